@@ -1,26 +1,26 @@
 -- Insert fake guests
-INSERT INTO guests (github_id, name, username, access_token)
+INSERT INTO guests (external_id, name, username, access_token)
 VALUES
-(1001, 'Alice Johnson', 'alice_j', 'token_alice'),
-(1002, 'Bob Smith', 'bob_s', 'token_bob'),
-(1003, 'Charlie Brown', 'charlie_b', 'token_charlie'),
-(1004, 'Diana Prince', 'diana_p', 'token_diana'),
-(1005, 'Evan White', 'evan_w', 'token_evan'),
-(1006, 'Fiona Green', 'fiona_g', 'token_fiona'),
-(1007, 'George Black', 'george_b', 'token_george'),
-(1008, 'Hannah Red', 'hannah_r', 'token_hannah'),
-(1009, 'Ian Blue', 'ian_b', 'token_ian'),
-(1010, 'Julia Yellow', 'julia_y1', 'token_julia'),
-(1101, 'Alice Johnson', 'alice_j1', 'token_alice'),
-(1102, 'Bob Smith', 'bob_s1', 'token_bob'),
-(1103, 'Charlie Brown', 'charlie_b1', 'token_charlie'),
-(1104, 'Diana Prince', 'diana_p1', 'token_diana'),
-(1105, 'Evan White', 'evan_w1', 'token_evan'),
-(1106, 'Fiona Green', 'fiona_g1', 'token_fiona'),
-(1107, 'George Black', 'george_b1', 'token_george'),
-(1108, 'Hannah Red', 'hannah_r1', 'token_hannah'),
-(1109, 'Ian Blue', 'ian_b1', 'token_ian'),
-(1110, 'Julia Yellow', 'julia_y1', 'token_julia');
+('1001', 'Alice Johnson', 'alice_j', 'token_alice'),
+('1002', 'Bob Smith', 'bob_s', 'token_bob'),
+('1003', 'Charlie Brown', 'charlie_b', 'token_charlie'),
+('1004', 'Diana Prince', 'diana_p', 'token_diana'),
+('1005', 'Evan White', 'evan_w', 'token_evan'),
+('1006', 'Fiona Green', 'fiona_g', 'token_fiona'),
+('1007', 'George Black', 'george_b', 'token_george'),
+('1008', 'Hannah Red', 'hannah_r', 'token_hannah'),
+('1009', 'Ian Blue', 'ian_b', 'token_ian'),
+('1010', 'Julia Yellow', 'julia_y1', 'token_julia'),
+('1101', 'Alice Johnson', 'alice_j1', 'token_alice'),
+('1102', 'Bob Smith', 'bob_s1', 'token_bob'),
+('1103', 'Charlie Brown', 'charlie_b1', 'token_charlie'),
+('1104', 'Diana Prince', 'diana_p1', 'token_diana'),
+('1105', 'Evan White', 'evan_w1', 'token_evan'),
+('1106', 'Fiona Green', 'fiona_g1', 'token_fiona'),
+('1107', 'George Black', 'george_b1', 'token_george'),
+('1108', 'Hannah Red', 'hannah_r1', 'token_hannah'),
+('1109', 'Ian Blue', 'ian_b1', 'token_ian'),
+('1110', 'Julia Yellow', 'julia_y1', 'token_julia');
 
 
 -- Insert fake guestbook entries with signatures
@@ -0,0 +1,7 @@
+-- Guestbook permissions are granted through the guests group, so enroll every
+-- existing account that predates group-based authorization.
+INSERT INTO guests_groups (guest_id, group_id)
+SELECT g.id, gr.id
+FROM guests g, groups gr
+WHERE gr.name = 'guests'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,9 @@
+-- Server-side secret backing the session auth hash, independent of OAuth tokens.
+-- Rotating it invalidates every session of the guest.
+ALTER TABLE guests
+ADD COLUMN session_secret TEXT NOT NULL
+    DEFAULT replace(gen_random_uuid()::text || gen_random_uuid()::text, '-', '');
+
+-- Access tokens are encrypted at rest from now on; drop the plaintext ones.
+-- They are replaced on the next login.
+UPDATE guests SET access_token = '';
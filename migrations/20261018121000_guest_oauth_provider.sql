@@ -0,0 +1,22 @@
+-- Guests can sign in with providers other than GitHub.
+CREATE TYPE oauthprovider AS ENUM ('github', 'gitlab', 'oidc');
+
+ALTER TABLE guests
+    ADD COLUMN provider oauthprovider NOT NULL DEFAULT 'github',
+    ADD COLUMN external_id TEXT;
+
+UPDATE guests SET external_id = github_id::text;
+
+ALTER TABLE guests ALTER COLUMN external_id SET NOT NULL;
+
+DROP INDEX IF EXISTS idx_guests_github_id;
+ALTER TABLE guests DROP COLUMN github_id;
+
+-- The same identity may only exist once per provider.
+ALTER TABLE guests ADD CONSTRAINT guests_provider_external_id_key UNIQUE (provider, external_id);
+
+-- Usernames are only unique within a provider. Entries are tied to their author by
+-- `author_id`, so the denormalised username no longer needs a foreign key.
+ALTER TABLE guestbook DROP CONSTRAINT guestbook_author_username_fkey;
+ALTER TABLE guests DROP CONSTRAINT guests_username_key;
+ALTER TABLE guests ADD CONSTRAINT guests_provider_username_key UNIQUE (provider, username);
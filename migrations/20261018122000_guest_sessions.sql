@@ -0,0 +1,22 @@
+-- The session store creates this table on startup. Creating it here too, with the
+-- same definition, lets `guest_sessions` reference it.
+CREATE SCHEMA IF NOT EXISTS tower_sessions;
+CREATE TABLE IF NOT EXISTS tower_sessions.session (
+    id              TEXT PRIMARY KEY NOT NULL,
+    data            BYTEA NOT NULL,
+    expiry_date     TIMESTAMPTZ NOT NULL
+);
+
+-- Metadata about the sessions of logged in guests. Rows disappear together with the
+-- session, whether it expires, is logged out or revoked.
+CREATE TABLE IF NOT EXISTS guest_sessions (
+    id              BIGSERIAL PRIMARY KEY,
+    session_id      TEXT NOT NULL UNIQUE REFERENCES tower_sessions.session(id) ON DELETE CASCADE,
+    guest_id        BIGINT NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    created_at      TIMESTAMP WITH TIME ZONE DEFAULT current_timestamp NOT NULL,
+    last_seen_at    TIMESTAMP WITH TIME ZONE DEFAULT current_timestamp NOT NULL,
+    user_agent      TEXT,
+    ip              TEXT
+);
+
+CREATE INDEX idx_guest_sessions_guest_id ON guest_sessions(guest_id);
@@ -0,0 +1,7 @@
+-- Full-text search over guestbook messages.
+CREATE INDEX IF NOT EXISTS idx_guestbook_message_search
+    ON guestbook USING GIN (to_tsvector('english', message));
+
+-- Lookups by author and keyset pagination.
+CREATE INDEX IF NOT EXISTS idx_guestbook_author_username ON guestbook (lower(author_username));
+CREATE INDEX IF NOT EXISTS idx_guestbook_created_at_id ON guestbook (created_at DESC, id DESC);
@@ -0,0 +1,3 @@
+-- Guests may react to entries. The new value can only be used once this migration
+-- has been committed, so it is granted in the next one.
+ALTER TYPE permissionvariant ADD VALUE IF NOT EXISTS 'react';
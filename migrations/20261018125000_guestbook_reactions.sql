@@ -0,0 +1,19 @@
+-- Reactions guests leave on guestbook entries, from a fixed set of emoji.
+CREATE TYPE reactionkind AS ENUM ('heart', 'thumbsup', 'laugh', 'wave', 'fire');
+
+CREATE TABLE IF NOT EXISTS guestbook_reactions (
+    entry_id        BIGINT NOT NULL REFERENCES guestbook(id) ON DELETE CASCADE,
+    guest_id        BIGINT NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    reaction        reactionkind NOT NULL,
+    created_at      TIMESTAMP WITH TIME ZONE DEFAULT current_timestamp NOT NULL,
+    PRIMARY KEY     (entry_id, guest_id, reaction)
+);
+
+CREATE INDEX IF NOT EXISTS idx_guestbook_reactions_guest_id ON guestbook_reactions(guest_id);
+
+INSERT INTO permissions (name) VALUES ('react');
+
+INSERT INTO groups_permissions (group_id, permission_id)
+SELECT g.id, p.id
+FROM groups g, permissions p
+WHERE g.name = 'guests' AND p.name = 'react';
@@ -0,0 +1,12 @@
+-- An optional reply from the site owner on each entry.
+ALTER TABLE guestbook
+    ADD COLUMN reply TEXT,
+    ADD COLUMN replied_at TIMESTAMP WITH TIME ZONE,
+    ADD CONSTRAINT reply_has_timestamp CHECK ((reply IS NULL) = (replied_at IS NULL));
+
+INSERT INTO permissions (name) VALUES ('replytosignature');
+
+INSERT INTO groups_permissions (group_id, permission_id)
+SELECT g.id, p.id
+FROM groups g, permissions p
+WHERE g.name = 'admins' AND p.name = 'replytosignature';
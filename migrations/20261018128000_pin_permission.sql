@@ -0,0 +1,3 @@
+-- Admins may pin entries to the top of the guestbook. The new value can only be used
+-- once this migration has been committed, so it is granted in the next one.
+ALTER TYPE permissionvariant ADD VALUE IF NOT EXISTS 'pinsignature';
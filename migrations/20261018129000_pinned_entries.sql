@@ -0,0 +1,15 @@
+-- Pinned entries are listed first, most recently pinned first.
+ALTER TABLE guestbook ADD COLUMN pinned_at TIMESTAMP WITH TIME ZONE;
+
+-- Unpinned entries sort as pinned at -infinity, so the listing is ordered by a single
+-- key that keyset pagination can compare against.
+CREATE INDEX guestbook_pinned_order_idx ON guestbook (
+    (COALESCE(pinned_at, '-infinity'::timestamptz)) DESC, created_at DESC, id DESC
+);
+
+INSERT INTO permissions (name) VALUES ('pinsignature');
+
+INSERT INTO groups_permissions (group_id, permission_id)
+SELECT g.id, p.id
+FROM groups g, permissions p
+WHERE g.name = 'admins' AND p.name = 'pinsignature';
@@ -0,0 +1,3 @@
+-- Guests may report entries to the moderators. The new value can only be used once
+-- this migration has been committed, so it is granted in the next one.
+ALTER TYPE permissionvariant ADD VALUE IF NOT EXISTS 'reportsignature';
@@ -0,0 +1,24 @@
+-- Reports guests file against guestbook entries, awaiting review by a moderator.
+CREATE TYPE reportreason AS ENUM ('spam', 'offensive', 'drawing', 'other');
+
+CREATE TABLE IF NOT EXISTS reports (
+    id              BIGSERIAL PRIMARY KEY,
+    entry_id        BIGINT NOT NULL REFERENCES guestbook(id) ON DELETE CASCADE,
+    reporter_id     BIGINT NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    reason          reportreason NOT NULL,
+    created_at      TIMESTAMP WITH TIME ZONE DEFAULT current_timestamp NOT NULL,
+    UNIQUE          (entry_id, reporter_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_reports_reporter_id ON reports(reporter_id);
+
+-- Entries are hidden from the public listing once they received enough reports, until
+-- a moderator dismisses the reports.
+ALTER TABLE guestbook ADD COLUMN hidden_at TIMESTAMP WITH TIME ZONE;
+
+INSERT INTO permissions (name) VALUES ('reportsignature');
+
+INSERT INTO groups_permissions (group_id, permission_id)
+SELECT g.id, p.id
+FROM groups g, permissions p
+WHERE g.name = 'guests' AND p.name = 'reportsignature';
@@ -0,0 +1,14 @@
+-- Bootstrap admins promoted so far. Each guest is only promoted once, so demoting a
+-- bootstrap admin sticks across logins.
+CREATE TABLE IF NOT EXISTS bootstrap_promotions (
+    guest_id        BIGINT PRIMARY KEY REFERENCES guests(id) ON DELETE CASCADE,
+    promoted_at     TIMESTAMP WITH TIME ZONE DEFAULT current_timestamp NOT NULL
+);
+
+-- Current admins were promoted already, whether by bootstrapping or by hand.
+INSERT INTO bootstrap_promotions (guest_id)
+SELECT gg.guest_id
+FROM guests_groups gg
+JOIN groups g ON g.id = gg.group_id
+WHERE g.name = 'admins'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,3 @@
+-- Usernames can change and OIDC `preferred_username` claims need not be unique, so
+-- guests are only identified by `(provider, external_id)`.
+ALTER TABLE guests DROP CONSTRAINT IF EXISTS guests_provider_username_key;
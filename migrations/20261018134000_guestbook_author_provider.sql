@@ -0,0 +1,5 @@
+-- Entries remember the provider their author signed in with, so that the author can
+-- be linked to the right profile.
+ALTER TABLE guestbook ADD COLUMN author_provider oauthprovider NOT NULL DEFAULT 'github';
+
+UPDATE guestbook gb SET author_provider = g.provider FROM guests g WHERE g.id = gb.author_id;
@@ -1,10 +1,12 @@
-use crate::shared::models::{Guest, GuestbookEntry};
+use crate::shared::models::{GuestbookEntry, PublicGuest};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UserState {
-    pub guest: Guest,
+    pub guest: PublicGuest,
     pub entry: Option<GuestbookEntry>,
+    /// Whether the guest has been marked as naughty and may no longer sign.
+    pub naughty: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -4,13 +4,15 @@
 //! configuration for the application. It's designed to be shared across
 //! different parts of the application, particularly in request handlers.
 use crate::{
-    backend::{db::DbConnPool, repos::{GroupsAndPermissionsRepo, PgRepository}},
+    backend::{
+        db::DbConnPool,
+        repos::{GroupsAndPermissionsRepo, PgRepository, ReactionRepo, ReportRepo, SessionRepo},
+    },
     shared::models::{Guest, GuestbookEntry},
 };
 use axum::extract::FromRef;
 use axum_extra::extract::cookie::Key;
 use reqwest::Client as ReqwestClient;
-use super::domain::logic::oauth::SetOauthClient;
 /// Represents the shared state of the application.
 ///
 /// This struct holds various shared resources and configuration that can be
@@ -27,12 +29,18 @@ pub struct AppState {
     pub guestbook_repo: PgRepository<GuestbookEntry>,
     /// Repository for user and group permissions
     pub gp_repo: GroupsAndPermissionsRepo,
+    /// Repository for the sessions guests are logged in with
+    pub session_repo: SessionRepo,
+    /// Repository for reactions on guestbook entries
+    pub reaction_repo: ReactionRepo,
+    /// Repository for reports filed against guestbook entries
+    pub report_repo: ReportRepo,
     /// The domain name of the application.
     pub domain: String,
     /// A key used for signing and verifying cookies.
     pub key: Key,
-    /// The client for OAuth2 requests.
-    pub client: SetOauthClient,
+    /// Origins that post-login redirects may point at, besides relative paths.
+    pub allowed_redirects: Vec<String>,
 }
 /// Allows extracting the `Key` from `AppState`.
 impl FromRef<AppState> for Key {
@@ -50,8 +58,10 @@ impl AppState {
     ///
     /// * `db` - The database connection pool.
     /// * `domain` - The domain name of the application.
-    /// * `client` - The client for the OAuth2 requests.
     /// * `reqwest_client` - The reqwest client.
+    /// * `key` - The key used for signing cookies.
+    /// * `allowed_redirects` - Origins that post-login redirects may point at.
+    /// * `report_threshold` - The number of reports after which an entry is hidden.
     ///
     /// # Returns
     ///
@@ -59,8 +69,10 @@ impl AppState {
     pub fn new(
         db: DbConnPool,
         domain: String,
-        client: SetOauthClient,
         reqwest_client: ReqwestClient,
+        key: Key,
+        allowed_redirects: Vec<String>,
+        report_threshold: i64,
     ) -> Self {
         Self {
             db: db.clone(),
@@ -68,9 +80,12 @@ impl AppState {
             guest_repo: PgRepository::new(db.clone()),
             guestbook_repo: PgRepository::new(db.clone()),
             gp_repo: GroupsAndPermissionsRepo::new(db.clone()),
+            session_repo: SessionRepo::new(db.clone()),
+            reaction_repo: ReactionRepo::new(db.clone()),
+            report_repo: ReportRepo::new(db.clone(), report_threshold),
             domain,
-            client,
-            key: Key::generate(),
+            key,
+            allowed_redirects,
         }
     }
 }
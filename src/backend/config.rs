@@ -1,31 +1,196 @@
 #![allow(unused)]
 use crate::hide::Hide;
+use crate::shared::models::{Guest, Provider};
 use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
+/// The environment the server runs in.
+///
+/// It selects the `config/{mode}.toml` file and decides whether missing secrets may be
+/// replaced with random ones, which is only allowed in development.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunMode {
+    Development,
+    #[default]
+    Production,
+}
+impl RunMode {
+    /// Reads the run mode from `RUN_MODE`, which defaults to development in debug builds
+    /// and to production otherwise.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        match std::env::var("RUN_MODE").ok().as_deref() {
+            Some("development") => Ok(Self::Development),
+            Some("production") => Ok(Self::Production),
+            Some(other) => Err(ConfigError::Message(format!("Unknown RUN_MODE {other}"))),
+            None if cfg!(debug_assertions) => Ok(Self::Development),
+            None => Ok(Self::Production),
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunMode::Development => "development",
+            RunMode::Production => "production",
+        }
+    }
+}
 #[derive(Debug, Deserialize)]
 pub struct RateLimiting {
     pub requests_per_second: u64,
     pub burst_size: u32,
 }
+/// Credentials and endpoints for the GitHub OAuth application.
+///
+/// Unset endpoints fall back to GitHub's, see `GitHub::default()`. They only need to
+/// be set to point the OAuth flow at a mock server.
 #[derive(Debug, Deserialize)]
 pub struct GabioinfConfig {
     pub id: String,
     pub secret: Hide<String>,
+    /// Authorization endpoint.
+    pub authorize: Option<String>,
+    /// Token endpoint.
+    pub token: Option<String>,
+    /// Base URL of the REST API.
+    pub api: Option<String>,
+}
+/// Credentials for a GitLab OAuth application.
+#[derive(Debug, Deserialize)]
+pub struct GitlabConfig {
+    pub id: String,
+    pub secret: Hide<String>,
+    /// Base URL of the GitLab instance.
+    #[serde(default = "default_gitlab_url")]
+    pub url: String,
+}
+fn default_gitlab_url() -> String {
+    "https://gitlab.com".to_string()
+}
+/// Credentials and endpoints for a generic OpenID Connect provider.
+///
+/// Sign-in only uses OAuth2 and the user-info endpoint; ID tokens are not validated, so
+/// `userinfo` has to be the provider's own endpoint.
+#[derive(Debug, Deserialize)]
+pub struct OidcConfig {
+    pub id: String,
+    pub secret: Hide<String>,
+    /// Authorization endpoint.
+    pub authorize: String,
+    /// Token endpoint.
+    pub token: String,
+    /// User-info endpoint.
+    pub userinfo: String,
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: Vec<String>,
+}
+fn default_oidc_scopes() -> Vec<String> {
+    vec!["openid".to_string(), "profile".to_string()]
+}
+/// Optional OAuth providers in addition to GitHub, which is configured in
+/// [`GabioinfConfig`].
+#[derive(Debug, Default, Deserialize)]
+pub struct ProvidersConfig {
+    pub gitlab: Option<GitlabConfig>,
+    pub oidc: Option<OidcConfig>,
 }
 #[derive(Debug, Deserialize)]
 pub struct DatabaseConfig {
     pub url: Hide<String>,
 }
+/// Keys used to sign session cookies, hex or base64 encoded (at least 64 bytes).
+#[derive(Debug, Default, Deserialize)]
+pub struct SessionConfig {
+    /// The key used to sign new cookies. Required in production.
+    pub key: Option<Hide<String>>,
+    /// Previously used keys that are still accepted while rotating.
+    #[serde(default)]
+    pub previous: Vec<Hide<String>>,
+}
+/// Key used to encrypt access tokens at rest, hex or base64 encoded (32 bytes).
+#[derive(Debug, Default, Deserialize)]
+pub struct EncryptionConfig {
+    /// The encryption key. Required in production.
+    pub key: Option<Hide<String>>,
+}
+/// Where users may be sent back to after logging in.
+#[derive(Debug, Default, Deserialize)]
+pub struct RedirectConfig {
+    /// Origins such as `https://gabioinf.com` that the `next` parameter may point at.
+    /// Relative paths on this site are always allowed.
+    #[serde(default)]
+    pub allowed: Vec<String>,
+}
+/// Settings for the moderation of guestbook entries.
+#[derive(Debug, Deserialize)]
+pub struct ModerationConfig {
+    /// The number of reports after which an entry is hidden until a moderator reviews it.
+    #[serde(default = "default_report_threshold")]
+    pub threshold: i64,
+}
+impl ModerationConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.threshold < 1 {
+            return Err(ConfigError::Message("moderation.threshold must be at least 1".into()));
+        }
+        Ok(())
+    }
+}
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            threshold: default_report_threshold(),
+        }
+    }
+}
+fn default_report_threshold() -> i64 {
+    3
+}
+/// GitHub accounts that are promoted to admins the first time they log in.
+///
+/// Only GitHub identities are matched, so an account with the same login at another
+/// provider is not promoted. A bootstrap admin who is later demoted stays demoted.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BootstrapAdmins {
+    /// GitHub logins, matched case-insensitively.
+    #[serde(default)]
+    pub logins: Vec<String>,
+    /// GitHub user IDs.
+    #[serde(default)]
+    pub github_ids: Vec<i64>,
+}
+impl BootstrapAdmins {
+    /// Checks whether the given guest is listed as a bootstrap admin.
+    pub fn contains(&self, guest: &Guest) -> bool {
+        if guest.provider != Provider::Github {
+            return false;
+        }
+        self.github_ids.iter().any(|id| id.to_string() == guest.external_id.as_value())
+            || self.logins.iter().any(|login| login.eq_ignore_ascii_case(&guest.username))
+    }
+}
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
+    /// The run mode the configuration was loaded for.
+    #[serde(skip)]
+    pub run_mode: RunMode,
     pub domain: String,
     pub ratelimiting: RateLimiting,
     pub database: DatabaseConfig,
     pub gabioinf: GabioinfConfig,
+    #[serde(default)]
+    pub admins: BootstrapAdmins,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+    #[serde(default)]
+    pub providers: ProvidersConfig,
+    #[serde(default)]
+    pub redirects: RedirectConfig,
+    #[serde(default)]
+    pub moderation: ModerationConfig,
 }
 impl AppConfig {
     pub fn new<S: AsRef<str>>(base: S) -> Result<Self, ConfigError> {
-        let run_mode = if cfg!(debug_assertions) { "development" } else { "production" };
+        let run_mode = RunMode::from_env()?;
         let domain = std::env::var("DOMAIN_URL").ok();
         let base = base.as_ref();
         let mut s = Config::builder()
@@ -33,7 +198,8 @@ impl AppConfig {
                 File::with_name(&format!("{base}/config/default")).required(true),
             )
             .add_source(
-                File::with_name(&format!("{base}/config/{run_mode}")).required(false),
+                File::with_name(&format!("{base}/config/{}", run_mode.as_str()))
+                    .required(false),
             )
             .add_source(
                 Environment::with_prefix("DATABASE")
@@ -41,6 +207,46 @@ impl AppConfig {
                     .separator("_")
                     .convert_case(config::Case::Lower),
             )
+            .add_source(
+                Environment::with_prefix("SESSION")
+                    .keep_prefix(true)
+                    .separator("_")
+                    .convert_case(config::Case::Lower)
+                    .list_separator(",")
+                    .with_list_parse_key("session.previous")
+                    .try_parsing(true),
+            )
+            .add_source(
+                Environment::with_prefix("ENCRYPTION")
+                    .keep_prefix(true)
+                    .separator("_")
+                    .convert_case(config::Case::Lower),
+            )
+            .add_source(
+                Environment::with_prefix("PROVIDERS")
+                    .keep_prefix(true)
+                    .separator("_")
+                    .convert_case(config::Case::Lower)
+                    .list_separator(",")
+                    .with_list_parse_key("providers.oidc.scopes")
+                    .try_parsing(true),
+            )
+            .add_source(
+                Environment::with_prefix("REDIRECTS")
+                    .keep_prefix(true)
+                    .separator("_")
+                    .convert_case(config::Case::Lower)
+                    .list_separator(",")
+                    .with_list_parse_key("redirects.allowed")
+                    .try_parsing(true),
+            )
+            .add_source(
+                Environment::with_prefix("MODERATION")
+                    .keep_prefix(true)
+                    .separator("_")
+                    .convert_case(config::Case::Lower)
+                    .try_parsing(true),
+            )
             .add_source(
                 Environment::with_prefix("GABIOINF")
                     .keep_prefix(true)
@@ -49,7 +255,12 @@ impl AppConfig {
             )
             .set_override_option("domain", domain)?
             .build()?;
-        s.try_deserialize()
+        let config = Self {
+            run_mode,
+            ..s.try_deserialize::<Self>()?
+        };
+        config.moderation.validate()?;
+        Ok(config)
     }
     pub fn new_local() -> Result<Self, ConfigError> {
         Self::new(".")
@@ -63,5 +274,35 @@ mod tests {
         let config = AppConfig::new("./").unwrap();
         assert_eq!(config.ratelimiting.requests_per_second, 5);
         assert_eq!(config.ratelimiting.burst_size, 10);
+        assert_eq!(config.moderation.threshold, 3);
+    }
+    #[test]
+    fn test_moderation_config() {
+        assert!(ModerationConfig::default().validate().is_ok());
+        assert!(ModerationConfig { threshold: 0 }.validate().is_err());
+    }
+    #[test]
+    fn test_bootstrap_admins() {
+        let admins = BootstrapAdmins {
+            logins: vec!["LilDojd".to_string()],
+            github_ids: vec![42],
+        };
+        let by_login = Guest {
+            username: "lildojd".to_string(),
+            ..Default::default()
+        };
+        let by_id = Guest {
+            external_id: 42.into(),
+            username: "someone".to_string(),
+            ..Default::default()
+        };
+        let other_provider = Guest {
+            provider: Provider::Gitlab,
+            ..by_login.clone()
+        };
+        assert!(admins.contains(&by_login));
+        assert!(admins.contains(&by_id));
+        assert!(!admins.contains(&other_provider));
+        assert!(!admins.contains(&Guest::default()));
     }
 }
@@ -0,0 +1,121 @@
+//! Self-service handling of a guest's own account.
+use crate::backend::errors::{ApiError, BResult};
+use crate::backend::repos::{GuestbookEntryCriteria, Repository};
+use crate::backend::AppState;
+use crate::shared::models::{AccountExport, Guest, GuestbookEntry};
+use time::OffsetDateTime;
+/// Collects everything stored about `guest` for the data export.
+///
+/// # Arguments
+///
+/// * `state` - The application state holding the repositories
+/// * `guest` - The guest whose data is exported
+/// * `current_session` - The ID of the session making the request, if any
+pub async fn export_account(
+    state: &AppState,
+    guest: Guest,
+    current_session: Option<&str>,
+) -> BResult<AccountExport> {
+    let entry = match state
+        .guestbook_repo
+        .read(&GuestbookEntryCriteria::WithAuthorId(guest.id))
+        .await
+    {
+        Ok(entry) => Some(entry),
+        Err(ApiError::DatabaseError(sqlx::Error::RowNotFound)) => None,
+        Err(e) => return Err(e),
+    };
+    let signature = entry.as_ref().and_then(|entry| entry.signature.clone());
+    let groups = state.gp_repo.get_user_groups(guest.id).await?;
+    let permissions = state.gp_repo.get_all_user_permissions(guest.id).await?;
+    let sessions = state.session_repo.list_active(guest.id).await?;
+    let reactions = state.reaction_repo.by_guest(guest.id).await?;
+    let reports = state.report_repo.by_reporter(guest.id).await?;
+    Ok(AccountExport {
+        exported_at: OffsetDateTime::now_utc(),
+        entry: entry.map(|entry| GuestbookEntry {
+            signature: None,
+            ..entry
+        }),
+        signature,
+        groups: groups.iter().map(|g| g.name().to_string()).collect(),
+        permissions: permissions.iter().map(|p| p.name().to_string()).collect(),
+        sessions: sessions.into_iter().map(|s| s.into_active(current_session)).collect(),
+        reactions,
+        reports,
+        profile: guest.into(),
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::domain::models::Group;
+    use crate::backend::utils::{setup_guest, setup_guests};
+    use crate::shared::models::{GuestId, Reaction, ReportReason};
+    use axum_extra::extract::cookie::Key;
+    use sqlx::PgPool;
+    fn state(pool: &PgPool) -> AppState {
+        AppState::new(
+            pool.clone(),
+            "localhost:8080".to_string(),
+            reqwest::Client::new(),
+            Key::generate(),
+            vec![],
+            3,
+        )
+    }
+    #[sqlx::test]
+    async fn test_export_without_entry(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let export = export_account(&state(&pool), guest.clone(), None).await.unwrap();
+        assert_eq!(export.profile.id, guest.id);
+        assert!(export.entry.is_none());
+        assert!(export.signature.is_none());
+        assert!(export.groups.is_empty());
+        assert!(export.reactions.is_empty());
+        assert!(export.reports.is_empty());
+    }
+    #[sqlx::test]
+    async fn test_export_with_entry(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let state = state(&pool);
+        let entry = GuestbookEntry {
+            message: "Hello".to_string(),
+            signature: Some("data:image/png;base64,AAAA".to_string()),
+            author_id: guest.id,
+            author_username: guest.username.clone(),
+            ..Default::default()
+        };
+        state.guestbook_repo.create(&entry).await.unwrap();
+        state.gp_repo.add_user_to_group(guest.id, Group::Guests).await.unwrap();
+        setup_guests(1, &pool).await;
+        let other = state
+            .guestbook_repo
+            .create(&GuestbookEntry {
+                message: "Buy cheap watches".to_string(),
+                author_id: GuestId(2),
+                author_username: "testuser1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        state.reaction_repo.add(other.id, guest.id, Reaction::Laugh).await.unwrap();
+        state.report_repo.report(other.id, guest.id, ReportReason::Spam).await.unwrap();
+        let export = export_account(&state, guest, None).await.unwrap();
+        let exported_entry = export.entry.unwrap();
+        assert_eq!(exported_entry.message, "Hello");
+        assert!(exported_entry.signature.is_none());
+        assert_eq!(export.signature.as_deref(), Some("data:image/png;base64,AAAA"));
+        assert_eq!(export.groups, vec!["guests"]);
+        assert!(export.permissions.contains(&"add_signature".to_string()));
+        assert_eq!(export.reactions.len(), 1);
+        assert_eq!(export.reactions[0].entry_id, other.id);
+        assert_eq!(export.reactions[0].reaction, Reaction::Laugh);
+        assert_eq!(export.reports.len(), 1);
+        assert_eq!(export.reports[0].entry_id, other.id);
+        assert_eq!(export.reports[0].reason, ReportReason::Spam);
+        let json = serde_json::to_value(&export).unwrap();
+        assert!(json["profile"].get("access_token").is_none());
+        assert!(json["profile"].get("session_secret").is_none());
+    }
+}
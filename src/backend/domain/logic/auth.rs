@@ -1,16 +1,22 @@
-use crate::backend::domain::logic::{oauth::CSRF_STATE_KEY, AuthSession};
-use axum::{
-    response::{IntoResponse, Redirect},
-    routing::get, Form, Router,
+use crate::backend::{
+    domain::logic::{oauth::CSRF_STATE_KEY, redirect::resolve_next, AuthSession},
+    errors::BResult,
+    AppState,
 };
+use crate::shared::models::Provider;
+use axum::{extract::State, response::Redirect, routing::get, Form, Router};
 use axum_login::tower_sessions::Session;
 use serde::Deserialize;
 pub const NEXT_URL_KEY: &str = "auth.next-url";
+pub const PROVIDER_KEY: &str = "auth.provider";
 #[derive(Debug, Deserialize)]
-pub struct NextUrl {
+pub struct LoginParams {
     next: Option<String>,
+    /// The provider to sign in with, GitHub if omitted.
+    #[serde(default)]
+    provider: Provider,
 }
-pub fn router() -> Router<()> {
+pub fn router() -> Router<AppState> {
     Router::new().route("/login", get(self::get::login))
 }
 mod get {
@@ -18,18 +24,24 @@ mod get {
     pub async fn login(
         auth_session: AuthSession,
         session: Session,
-        Form(NextUrl { next }): Form<NextUrl>,
-    ) -> impl IntoResponse {
-        dioxus_logger::tracing::info!("Hit login route");
-        let (auth_url, csrf_state) = auth_session.backend.authorize_url_unscoped();
+        State(state): State<AppState>,
+        Form(LoginParams { next, provider }): Form<LoginParams>,
+    ) -> BResult<Redirect> {
+        dioxus_logger::tracing::info!("Hit login route for {provider}");
+        let (auth_url, csrf_state) = auth_session.backend.authorize_url(provider)?;
         session
             .insert(CSRF_STATE_KEY, csrf_state.secret())
             .await
             .expect("Serialization should not fail.");
+        session
+            .insert(PROVIDER_KEY, provider)
+            .await
+            .expect("Serialization should not fail.");
+        let next = resolve_next(next.as_deref(), &state.allowed_redirects);
         session
             .insert(NEXT_URL_KEY, next)
             .await
             .expect("Serialization should not fail.");
-        Redirect::to(auth_url.as_str())
+        Ok(Redirect::to(auth_url.as_str()))
     }
 }
@@ -1,47 +1,126 @@
 use crate::{
     backend::{
-        domain::models::{Credentials, PermissionTargets},
+        config::BootstrapAdmins,
+        domain::models::{Credentials, Group, PermissionTargets},
         errors::{ApiError, BResult},
         repos::{GroupsAndPermissionsRepo, GuestCriteria, PgRepository, Repository},
+        token_cipher::TokenCipher,
     },
-    shared::models::{Guest, NewGuest},
+    shared::models::{Guest, GuestbookEntry, Provider},
 };
 use axum_login::{AuthnBackend, AuthzBackend, UserId};
-use oauth2::{
-    http::header::{AUTHORIZATION, USER_AGENT},
-    AuthorizationCode, CsrfToken, Scope, TokenResponse,
-};
+use oauth2::CsrfToken;
 use reqwest::Url;
 use std::collections::HashSet;
 #[derive(Clone, Debug)]
 pub struct AuthBackend {
     guest_repo: PgRepository<Guest>,
     gp_repo: GroupsAndPermissionsRepo,
-    client: SetOauthClient,
+    providers: Providers,
     reqwest_client: reqwest::Client,
+    admins: BootstrapAdmins,
+    cipher: TokenCipher,
 }
 impl AuthBackend {
     pub fn new(
         guest_repo: PgRepository<Guest>,
         gp_repo: GroupsAndPermissionsRepo,
-        client: SetOauthClient,
+        providers: Providers,
         reqwest_client: reqwest::Client,
+        admins: BootstrapAdmins,
+        cipher: TokenCipher,
     ) -> Self {
         Self {
             guest_repo,
             gp_repo,
-            client,
+            providers,
             reqwest_client,
+            admins,
+            cipher,
+        }
+    }
+    /// Upserts a guest on login and provisions their groups.
+    ///
+    /// Guests logging in for the first time are placed in [`Group::Guests`], and guests
+    /// listed in [`BootstrapAdmins`] are promoted to [`Group::Admins`] the first time
+    /// they are seen, so a later demotion is kept. Everything runs in a single
+    /// transaction.
+    pub async fn provision(&self, guest: Guest) -> BResult<Guest> {
+        let mut tx = self.guest_repo.pool.begin().await?;
+        let is_new = PgRepository::<Guest>::find_by_identity_with(
+                &mut *tx,
+                guest.provider,
+                &guest.external_id,
+            )
+            .await?
+            .is_none();
+        let guest = PgRepository::<Guest>::upsert_with(&mut *tx, &guest).await?;
+        if is_new {
+            dioxus_logger::tracing::info!("Enrolling new guest {} into guests", guest.id);
+            GroupsAndPermissionsRepo::add_user_to_group_with(&mut *tx, guest.id, Group::Guests)
+                .await?;
+        }
+        if self.admins.contains(&guest)
+            && GroupsAndPermissionsRepo::record_bootstrap_promotion_with(&mut *tx, guest.id)
+                .await?
+        {
+            dioxus_logger::tracing::info!("Promoting bootstrap admin {}", guest.id);
+            GroupsAndPermissionsRepo::add_user_to_group_with(&mut *tx, guest.id, Group::Admins)
+                .await?;
         }
+        tx.commit().await?;
+        Ok(guest)
+    }
+    /// Returns the configured OAuth providers.
+    pub fn providers(&self) -> &Providers {
+        &self.providers
     }
-    pub fn authorize_url<I>(&self, scopes: I) -> (Url, CsrfToken)
-    where
-        I: IntoIterator<Item = Scope>,
-    {
-        self.client.authorize_url(CsrfToken::new_random).add_scopes(scopes).url()
+    /// Builds the authorization URL for `provider`.
+    ///
+    /// # Returns
+    ///
+    /// The URL and CSRF token, or [`ApiError::NotFoundError`] if the provider is not
+    /// configured.
+    pub fn authorize_url(&self, provider: Provider) -> BResult<(Url, CsrfToken)> {
+        Ok(self.providers.get(provider)?.authorize_url())
     }
-    pub fn authorize_url_unscoped(&self) -> (Url, CsrfToken) {
-        self.authorize_url(std::iter::empty())
+    /// Ensures that `user` is authenticated and holds `permission`.
+    ///
+    /// # Returns
+    ///
+    /// The authorized [`Guest`], or [`ApiError::AuthorizationError`] if the user is
+    /// missing or lacks the permission.
+    pub async fn authorize(
+        &self,
+        user: Option<&Guest>,
+        permission: PermissionTargets,
+    ) -> BResult<Guest> {
+        let user = user.cloned().ok_or_else(unauthenticated)?;
+        if self.has_perm(&user, permission.clone()).await? {
+            Ok(user)
+        } else {
+            Err(ApiError::AuthorizationError(format!("Missing permission: {permission:?}")))
+        }
+    }
+    /// Ensures that `user` may act on `entry`.
+    ///
+    /// Access is granted if the user holds `any`, or holds `own` and authored the entry.
+    pub async fn authorize_entry(
+        &self,
+        user: Option<&Guest>,
+        entry: &GuestbookEntry,
+        own: PermissionTargets,
+        any: PermissionTargets,
+    ) -> BResult<Guest> {
+        let user = user.cloned().ok_or_else(unauthenticated)?;
+        let permissions = self.get_all_permissions(&user).await?;
+        if permissions.contains(&any)
+            || (permissions.contains(&own) && entry.author_id == user.id)
+        {
+            Ok(user)
+        } else {
+            Err(ApiError::AuthorizationError(format!("Missing permission: {own:?}")))
+        }
     }
 }
 impl AuthnBackend for AuthBackend {
@@ -55,29 +134,15 @@ impl AuthnBackend for AuthBackend {
         if creds.old_state.secret() != creds.new_state.secret() {
             return Ok(None);
         }
-        dioxus_logger::tracing::debug!("Received OAuth callback");
-        let token = self
-            .client
-            .exchange_code(AuthorizationCode::new(creds.code))
-            .request_async(&self.reqwest_client)
-            .await
-            .map_err(|e| Self::Error::AuthenticationError(e.to_string()))?;
-        dioxus_logger::tracing::debug!("Getting user data from GitHub API");
-        let response = reqwest::Client::new()
-            .get("https://api.github.com/user")
-            .header(USER_AGENT.as_str(), "ga-guestbook")
-            .header(
-                AUTHORIZATION.as_str(),
-                format!("Bearer {}", token.access_token().secret()),
-            )
-            .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
-            .await;
-        let github_user = response?.json::<NewGuest>().await?;
-        dioxus_logger::tracing::debug!(
-            "Received user data from GitHub: {:?}", github_user
-        );
-        let guest = self.guest_repo.create(&github_user.into()).await?;
+        dioxus_logger::tracing::debug!("Received OAuth callback from {}", creds.provider);
+        let provider = self.providers.get(creds.provider)?;
+        let access_token = provider.exchange_code(&self.reqwest_client, creds.code).await?;
+        dioxus_logger::tracing::debug!("Getting user data from {}", creds.provider);
+        let identity = provider.fetch_identity(&self.reqwest_client, &access_token).await?;
+        dioxus_logger::tracing::debug!("Received user data: {:?}", identity);
+        let mut guest: Guest = identity.into();
+        guest.access_token = self.cipher.encrypt(&access_token)?;
+        let guest = self.provision(guest).await?;
         Ok(Some(guest))
     }
     async fn get_user(&self, user_id: &UserId<Self>) -> BResult<Option<Self::User>> {
@@ -108,13 +173,52 @@ impl AuthzBackend for AuthBackend {
         Ok(perms.into_iter().collect())
     }
 }
+/// The error returned when an action requires a logged in user.
+fn unauthenticated() -> ApiError {
+    ApiError::AuthorizationError("User is not authenticated".to_string())
+}
 pub type AuthSession = axum_login::AuthSession<AuthBackend>;
 #[derive(Debug, Clone)]
 pub struct SessionWrapper {
     pub session: AuthSession,
 }
+impl SessionWrapper {
+    /// Returns the authenticated user or [`ApiError::AuthorizationError`].
+    pub fn require_user(&self) -> BResult<Guest> {
+        self.session
+            .user
+            .clone()
+            .ok_or_else(unauthenticated)
+    }
+    /// Checks whether the authenticated user holds `permission`.
+    ///
+    /// Anonymous visitors hold no permissions.
+    pub async fn has_permission(&self, permission: PermissionTargets) -> BResult<bool> {
+        match &self.session.user {
+            Some(user) => self.session.backend.has_perm(user, permission).await,
+            None => Ok(false),
+        }
+    }
+    /// Returns the authenticated user if they hold `permission`.
+    pub async fn require_permission(&self, permission: PermissionTargets) -> BResult<Guest> {
+        self.session.backend.authorize(self.session.user.as_ref(), permission).await
+    }
+    /// Returns the authenticated user if they may act on `entry`, see
+    /// [`AuthBackend::authorize_entry`].
+    pub async fn require_entry_permission(
+        &self,
+        entry: &GuestbookEntry,
+        own: PermissionTargets,
+        any: PermissionTargets,
+    ) -> BResult<Guest> {
+        self.session
+            .backend
+            .authorize_entry(self.session.user.as_ref(), entry, own, any)
+            .await
+    }
+}
 use axum::{extract::FromRequestParts, http::request::Parts};
-use super::oauth::SetOauthClient;
+use super::providers::Providers;
 #[derive(Debug)]
 pub struct StateError;
 impl std::error::Error for StateError {}
@@ -145,3 +249,148 @@ where
         }
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::domain::logic::providers::{ConfiguredProvider, GitHub};
+    use crate::backend::utils::setup_guest;
+    use crate::shared::models::GuestId;
+    use sqlx::PgPool;
+    fn backend(pool: &PgPool) -> AuthBackend {
+        AuthBackend::new(
+            PgRepository::new(pool.clone()),
+            GroupsAndPermissionsRepo::new(pool.clone()),
+            Providers::default()
+                .with(
+                    ConfiguredProvider::new(
+                        GitHub::default(),
+                        "id",
+                        "secret",
+                        "localhost:8080",
+                    ),
+                ),
+            reqwest::Client::new(),
+            BootstrapAdmins {
+                logins: vec!["boss".to_string()],
+                github_ids: Vec::new(),
+            },
+            TokenCipher::new(&[0u8; 32]).unwrap(),
+        )
+    }
+    #[sqlx::test]
+    async fn test_authorize_unauthenticated(pool: PgPool) {
+        let backend = backend(&pool);
+        let result = backend.authorize(None, PermissionTargets::AddSignature).await;
+        assert!(matches!(result, Err(ApiError::AuthorizationError(_))));
+    }
+    #[sqlx::test]
+    async fn test_authorize_without_group(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let backend = backend(&pool);
+        let result = backend.authorize(Some(&guest), PermissionTargets::AddSignature).await;
+        assert!(matches!(result, Err(ApiError::AuthorizationError(_))));
+    }
+    #[sqlx::test]
+    async fn test_authorize_guest_group(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let backend = backend(&pool);
+        backend.gp_repo.add_user_to_group(guest.id, Group::Guests).await.unwrap();
+        let authorized = backend
+            .authorize(Some(&guest), PermissionTargets::AddSignature)
+            .await
+            .unwrap();
+        assert_eq!(authorized.id, guest.id);
+        let result = backend
+            .authorize(Some(&guest), PermissionTargets::DeleteAnySignature)
+            .await;
+        assert!(matches!(result, Err(ApiError::AuthorizationError(_))));
+    }
+    #[sqlx::test]
+    async fn test_authorize_entry(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let backend = backend(&pool);
+        backend.gp_repo.add_user_to_group(guest.id, Group::Guests).await.unwrap();
+        let own_entry = GuestbookEntry {
+            author_id: guest.id,
+            ..Default::default()
+        };
+        let foreign_entry = GuestbookEntry {
+            author_id: GuestId(guest.id.as_value() + 1),
+            ..Default::default()
+        };
+        let (own, any) = (
+            PermissionTargets::DeleteOwnSignature,
+            PermissionTargets::DeleteAnySignature,
+        );
+        assert!(
+            backend
+                .authorize_entry(Some(&guest), &own_entry, own.clone(), any.clone())
+                .await
+                .is_ok()
+        );
+        let result = backend
+            .authorize_entry(Some(&guest), &foreign_entry, own.clone(), any.clone())
+            .await;
+        assert!(matches!(result, Err(ApiError::AuthorizationError(_))));
+        backend.gp_repo.add_user_to_group(guest.id, Group::Admins).await.unwrap();
+        assert!(
+            backend.authorize_entry(Some(&guest), &foreign_entry, own, any).await.is_ok()
+        );
+    }
+    fn github_guest(github_id: i64, username: &str) -> Guest {
+        Guest {
+            provider: Provider::Github,
+            external_id: github_id.into(),
+            name: username.to_string(),
+            username: username.to_string(),
+            ..Default::default()
+        }
+    }
+    #[sqlx::test]
+    async fn test_provision_new_guest(pool: PgPool) {
+        let backend = backend(&pool);
+        let guest = backend.provision(github_guest(100, "newcomer")).await.unwrap();
+        let groups = backend.gp_repo.get_user_groups(guest.id).await.unwrap();
+        assert!(matches!(groups.as_slice(), [Group::Guests]));
+        assert!(
+            backend.authorize(Some(&guest), PermissionTargets::AddSignature).await.is_ok()
+        );
+    }
+    #[sqlx::test]
+    async fn test_provision_returning_guest_keeps_groups(pool: PgPool) {
+        let backend = backend(&pool);
+        let guest = backend.provision(github_guest(101, "regular")).await.unwrap();
+        backend.gp_repo.remove_user_from_group(guest.id, Group::Guests).await.unwrap();
+        let guest = backend.provision(github_guest(101, "regular")).await.unwrap();
+        let groups = backend.gp_repo.get_user_groups(guest.id).await.unwrap();
+        assert!(groups.is_empty());
+    }
+    #[sqlx::test]
+    async fn test_provision_bootstrap_admin(pool: PgPool) {
+        let backend = backend(&pool);
+        let guest = backend.provision(github_guest(102, "Boss")).await.unwrap();
+        let groups = backend.gp_repo.get_user_groups(guest.id).await.unwrap();
+        assert!(groups.iter().any(|g| matches!(g, Group::Admins)));
+        assert!(groups.iter().any(|g| matches!(g, Group::Guests)));
+    }
+    #[sqlx::test]
+    async fn test_provision_demoted_bootstrap_admin(pool: PgPool) {
+        let backend = backend(&pool);
+        let guest = backend.provision(github_guest(102, "boss")).await.unwrap();
+        backend.gp_repo.set_group_membership(guest.id, Group::Admins, false).await.unwrap();
+        let guest = backend.provision(github_guest(102, "boss")).await.unwrap();
+        let groups = backend.gp_repo.get_user_groups(guest.id).await.unwrap();
+        assert!(matches!(groups.as_slice(), [Group::Guests]));
+    }
+    #[sqlx::test]
+    async fn test_provision_bootstrap_admin_other_provider(pool: PgPool) {
+        let backend = backend(&pool);
+        let impostor = Guest {
+            provider: Provider::Gitlab,
+            ..github_guest(102, "boss")
+        };
+        let guest = backend.provision(impostor).await.unwrap();
+        let groups = backend.gp_repo.get_user_groups(guest.id).await.unwrap();
+        assert!(matches!(groups.as_slice(), [Group::Guests]));
+    }
+}
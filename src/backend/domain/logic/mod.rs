@@ -1,4 +1,9 @@
+pub mod account;
 pub mod auth;
 mod auth_backend;
 pub mod oauth;
+pub mod providers;
+pub mod redirect;
+pub mod sessions;
+pub mod signatures;
 pub use auth_backend::*;
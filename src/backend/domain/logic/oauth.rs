@@ -1,11 +1,17 @@
 //! Handles OAuth flow, user auth and session management
-use crate::backend::domain::logic::auth::NEXT_URL_KEY;
+use crate::backend::domain::logic::auth::{NEXT_URL_KEY, PROVIDER_KEY};
+use crate::backend::domain::logic::providers::OAuthProvider;
+use crate::backend::domain::logic::redirect::resolve_next;
+use crate::backend::domain::logic::sessions::record_login;
 use crate::backend::domain::logic::AuthSession;
-use axum::http::StatusCode;
+use crate::backend::errors::{ApiError, BResult};
+use crate::backend::AppState;
+use crate::shared::models::Provider;
+use axum::http::{header::ACCEPT, HeaderMap};
 use axum::routing::get;
 use axum::{
-    extract::Query, response::{IntoResponse, Redirect},
-    Router,
+    extract::{Query, State}, response::{IntoResponse, Redirect, Response},
+    Json, Router,
 };
 use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, TokenUrl};
 use oauth2::{CsrfToken, EndpointNotSet, EndpointSet, RedirectUrl};
@@ -19,35 +25,41 @@ pub(crate) type SetOauthClient = BasicClient<
     EndpointNotSet,
     EndpointSet,
 >;
+/// The query the provider redirects back with.
+///
+/// On success it carries `code` and `state`; on failure, e.g. when the user denies
+/// access, it carries `error` and optionally `error_description` instead.
 #[derive(Debug, Clone, Deserialize)]
 pub struct AuthzResp {
-    code: String,
-    state: CsrfToken,
+    code: Option<String>,
+    state: Option<CsrfToken>,
+    error: Option<String>,
+    error_description: Option<String>,
 }
-pub fn router() -> Router<()> {
+pub fn router() -> Router<AppState> {
     Router::new().route("/oauth/callback", get(self::get::callback))
 }
-/// Builds an OAuth2 client for the GitHub OAuth provider
+/// Builds an OAuth2 client for the given OAuth provider
 /// # Arguments
 ///
+/// * `provider` - The provider whose endpoints the client talks to
 /// * `client_id` - The client ID for the OAuth application
 /// * `client_secret` - The client secret for the OAuth application
 /// * `domain` - The domain name which will be used for redirect URI for the OAuth application
 ///
 /// # Returns
 ///
-/// A `BasicClient` object for the GitHub OAuth provider
+/// A `BasicClient` object for the OAuth provider
 ///
 pub fn build_oauth_client<S: AsRef<str>>(
+    provider: &dyn OAuthProvider,
     client_id: S,
     client_secret: S,
     domain: S,
 ) -> SetOauthClient {
-    let auth_url = AuthUrl::new("https://github.com/login/oauth/authorize".to_string())
+    let auth_url = AuthUrl::new(provider.auth_url())
         .expect("Invalid authorization endpoint URL");
-    let token_url = TokenUrl::new(
-            "https://github.com/login/oauth/access_token".to_string(),
-        )
+    let token_url = TokenUrl::new(provider.token_url())
         .expect("Invalid token endpoint URL");
     let oauth_redirect_uri = if domain.as_ref().contains("localhost:") {
         let port = domain.as_ref().split(':').next_back().unwrap().split('/').next().unwrap();
@@ -69,30 +81,83 @@ mod get {
     pub async fn callback(
         mut auth_session: AuthSession,
         session: Session,
-        Query(AuthzResp { code, state: new_state }): Query<AuthzResp>,
-    ) -> impl IntoResponse {
-        let Ok(Some(old_state)) = session.get(CSRF_STATE_KEY).await else {
-            return StatusCode::BAD_REQUEST.into_response();
+        State(state): State<AppState>,
+        headers: HeaderMap,
+        Query(response): Query<AuthzResp>,
+    ) -> Response {
+        if let Err(e) = complete_login(&mut auth_session, &session, &state, &headers, response)
+            .await
+        {
+            dioxus_logger::tracing::warn!(error = %e, "OAuth callback failed");
+            return error_response(e, wants_json(&headers));
+        }
+        let next = session.remove::<String>(NEXT_URL_KEY).await.ok().flatten();
+        Redirect::to(&resolve_next(next.as_deref(), &state.allowed_redirects)).into_response()
+    }
+    /// Validates the provider's response, authenticates the user, logs them in and
+    /// records the new session.
+    async fn complete_login(
+        auth_session: &mut AuthSession,
+        session: &Session,
+        state: &AppState,
+        headers: &HeaderMap,
+        response: AuthzResp,
+    ) -> BResult<()> {
+        // Only a callback carrying the state of the pending login may touch the session,
+        // so forged callbacks cannot cancel it or report errors on its behalf.
+        let old_state = session.get::<CsrfToken>(CSRF_STATE_KEY).await.ok().flatten();
+        let (Some(old_state), Some(new_state)) = (old_state, response.state) else {
+            return Err(ApiError::OAuthStateMismatch);
+        };
+        if old_state.secret() != new_state.secret() {
+            return Err(ApiError::OAuthStateMismatch);
+        }
+        let _ = session.remove::<CsrfToken>(CSRF_STATE_KEY).await;
+        let provider = session.remove::<Provider>(PROVIDER_KEY).await.ok().flatten();
+        if let Some(error) = response.error {
+            return Err(ApiError::OAuthProviderError {
+                error,
+                description: response.error_description,
+            });
+        }
+        let (Some(provider), Some(code)) = (provider, response.code) else {
+            return Err(ApiError::OAuthStateMismatch);
         };
         let creds = Credentials {
+            provider,
             code,
             old_state,
             new_state,
         };
         let user = match auth_session.authenticate(creds).await {
             Ok(Some(user)) => user,
-            Ok(None) => {
-                return StatusCode::UNAUTHORIZED.into_response();
-            }
-            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            Ok(None) => return Err(ApiError::OAuthStateMismatch),
+            Err(axum_login::Error::Backend(e)) => return Err(e),
+            Err(e) => return Err(ApiError::UnhandledError(e.to_string())),
         };
-        if auth_session.login(&user).await.is_err() {
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-        if let Ok(Some(next)) = session.remove::<String>(NEXT_URL_KEY).await {
-            Redirect::to(&next).into_response()
+        auth_session
+            .login(&user)
+            .await
+            .map_err(|e| ApiError::UnhandledError(e.to_string()))?;
+        record_login(&state.session_repo, session, user.id, headers).await
+    }
+    fn wants_json(headers: &HeaderMap) -> bool {
+        headers
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("application/json"))
+    }
+    /// Reports a failed login as JSON for API clients, or by redirecting browsers to
+    /// the error page.
+    fn error_response(e: ApiError, json: bool) -> Response {
+        let reason = e.auth_error_reason();
+        if json {
+            let body = Json(
+                serde_json::json!({ "error": reason.code(), "message": reason.message() }),
+            );
+            (e.status_code(), body).into_response()
         } else {
-            Redirect::to("/").into_response()
+            Redirect::to(&format!("/auth/error?reason={}", reason.code())).into_response()
         }
     }
 }
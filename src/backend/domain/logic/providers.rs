@@ -0,0 +1,411 @@
+//! OAuth providers guests can sign in with.
+//!
+//! Each provider implements [`OAuthProvider`], which describes its endpoints and how to
+//! turn its user-info response into a [`NewGuest`]. Configured providers are collected
+//! in a [`Providers`] registry that the auth backend dispatches on.
+//!
+//! Every provider, [`Oidc`] included, identifies guests through its user-info endpoint;
+//! no ID tokens are validated.
+use crate::backend::{
+    config::AppConfig,
+    errors::{ApiError, BResult},
+};
+use crate::shared::models::{ExternalId, LoginProvider, NewGuest, Provider};
+use super::oauth::{build_oauth_client, SetOauthClient};
+use oauth2::{
+    http::header::{ACCEPT, AUTHORIZATION, USER_AGENT},
+    AuthorizationCode, CsrfToken, Scope, TokenResponse,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc};
+/// Describes an OAuth2 identity provider.
+pub trait OAuthProvider: std::fmt::Debug + Send + Sync {
+    /// The provider this implementation handles.
+    fn kind(&self) -> Provider;
+    /// The authorization endpoint users are redirected to.
+    fn auth_url(&self) -> String;
+    /// The endpoint used to exchange the authorization code for a token.
+    fn token_url(&self) -> String;
+    /// The endpoint returning the authenticated user's profile.
+    fn user_info_url(&self) -> String;
+    /// Scopes requested during authorization.
+    fn scopes(&self) -> Vec<Scope> {
+        Vec::new()
+    }
+    /// The URL that usernames are appended to for their public profile, if any.
+    fn profile_base(&self) -> Option<String> {
+        None
+    }
+    /// Extra headers sent along with the user-info request.
+    fn user_info_headers(&self) -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
+    /// Maps the user-info response to the identity of a guest.
+    fn map_identity(&self, info: serde_json::Value) -> BResult<NewGuest>;
+}
+fn parse_user_info<T: serde::de::DeserializeOwned>(info: serde_json::Value) -> BResult<T> {
+    serde_json::from_value(info)
+        .map_err(|e| ApiError::OAuthUserFetchError(format!("Malformed user info: {e}")))
+}
+/// GitHub OAuth apps.
+#[derive(Debug, Clone)]
+pub struct GitHub {
+    /// Authorization endpoint.
+    pub authorize: String,
+    /// Token endpoint.
+    pub token: String,
+    /// Base URL of the REST API.
+    pub api: String,
+}
+impl Default for GitHub {
+    fn default() -> Self {
+        Self {
+            authorize: "https://github.com/login/oauth/authorize".to_string(),
+            token: "https://github.com/login/oauth/access_token".to_string(),
+            api: "https://api.github.com".to_string(),
+        }
+    }
+}
+#[derive(Deserialize)]
+struct GitHubUser {
+    id: i64,
+    login: String,
+    name: Option<String>,
+}
+impl OAuthProvider for GitHub {
+    fn kind(&self) -> Provider {
+        Provider::Github
+    }
+    fn auth_url(&self) -> String {
+        self.authorize.clone()
+    }
+    fn token_url(&self) -> String {
+        self.token.clone()
+    }
+    fn user_info_url(&self) -> String {
+        format!("{}/user", self.api.trim_end_matches('/'))
+    }
+    fn profile_base(&self) -> Option<String> {
+        Some("https://github.com".to_string())
+    }
+    fn user_info_headers(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("X-GitHub-Api-Version", "2022-11-28")]
+    }
+    fn map_identity(&self, info: serde_json::Value) -> BResult<NewGuest> {
+        let user: GitHubUser = parse_user_info(info)?;
+        Ok(NewGuest {
+            provider: self.kind(),
+            external_id: user.id.into(),
+            username: user.login,
+            name: user.name,
+        })
+    }
+}
+/// GitLab, either gitlab.com or a self-hosted instance.
+#[derive(Debug, Clone)]
+pub struct GitLab {
+    /// Base URL of the instance, e.g. `https://gitlab.com`.
+    pub url: String,
+}
+#[derive(Deserialize)]
+struct GitLabUser {
+    id: i64,
+    username: String,
+    name: Option<String>,
+}
+impl OAuthProvider for GitLab {
+    fn kind(&self) -> Provider {
+        Provider::Gitlab
+    }
+    fn auth_url(&self) -> String {
+        format!("{}/oauth/authorize", self.url.trim_end_matches('/'))
+    }
+    fn token_url(&self) -> String {
+        format!("{}/oauth/token", self.url.trim_end_matches('/'))
+    }
+    fn user_info_url(&self) -> String {
+        format!("{}/api/v4/user", self.url.trim_end_matches('/'))
+    }
+    fn scopes(&self) -> Vec<Scope> {
+        vec![Scope::new("read_user".to_string())]
+    }
+    fn profile_base(&self) -> Option<String> {
+        Some(self.url.trim_end_matches('/').to_string())
+    }
+    fn map_identity(&self, info: serde_json::Value) -> BResult<NewGuest> {
+        let user: GitLabUser = parse_user_info(info)?;
+        Ok(NewGuest {
+            provider: self.kind(),
+            external_id: user.id.into(),
+            username: user.username,
+            name: user.name,
+        })
+    }
+}
+/// A generic OpenID Connect provider with explicitly configured endpoints.
+///
+/// This is plain OAuth2 plus the user-info endpoint, not a full OpenID Connect relying
+/// party: there is no discovery, and an `id_token` in the token response is neither
+/// requested nor validated, so its `iss`, `aud` and `nonce` claims are never checked.
+/// The guest's identity is whatever the configured user-info endpoint returns for the
+/// access token, so that endpoint must belong to the provider.
+#[derive(Debug, Clone)]
+pub struct Oidc {
+    pub authorize: String,
+    pub token: String,
+    pub userinfo: String,
+    pub scopes: Vec<String>,
+}
+#[derive(Deserialize)]
+struct OidcUser {
+    sub: String,
+    preferred_username: Option<String>,
+    nickname: Option<String>,
+    name: Option<String>,
+}
+impl OAuthProvider for Oidc {
+    fn kind(&self) -> Provider {
+        Provider::Oidc
+    }
+    fn auth_url(&self) -> String {
+        self.authorize.clone()
+    }
+    fn token_url(&self) -> String {
+        self.token.clone()
+    }
+    fn user_info_url(&self) -> String {
+        self.userinfo.clone()
+    }
+    fn scopes(&self) -> Vec<Scope> {
+        self.scopes.iter().cloned().map(Scope::new).collect()
+    }
+    fn map_identity(&self, info: serde_json::Value) -> BResult<NewGuest> {
+        let user: OidcUser = parse_user_info(info)?;
+        let username = user
+            .preferred_username
+            .or(user.nickname)
+            .unwrap_or_else(|| user.sub.clone());
+        Ok(NewGuest {
+            provider: self.kind(),
+            external_id: ExternalId::from(user.sub),
+            username,
+            name: user.name,
+        })
+    }
+}
+/// A provider together with the OAuth client built from its credentials.
+#[derive(Debug, Clone)]
+pub struct ConfiguredProvider {
+    provider: Arc<dyn OAuthProvider>,
+    client: SetOauthClient,
+}
+impl ConfiguredProvider {
+    /// Builds the OAuth client for `provider`.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider to sign in with
+    /// * `client_id` - The client ID for the OAuth application
+    /// * `client_secret` - The client secret for the OAuth application
+    /// * `domain` - The domain name which will be used for redirect URI for the OAuth application
+    pub fn new<P, S>(provider: P, client_id: S, client_secret: S, domain: S) -> Self
+    where
+        P: OAuthProvider + 'static,
+        S: AsRef<str>,
+    {
+        let client = build_oauth_client(&provider, client_id, client_secret, domain);
+        Self {
+            provider: Arc::new(provider),
+            client,
+        }
+    }
+    pub fn kind(&self) -> Provider {
+        self.provider.kind()
+    }
+    /// Describes the provider to the client.
+    pub fn login_provider(&self) -> LoginProvider {
+        LoginProvider {
+            kind: self.kind(),
+            profile_base: self.provider.profile_base(),
+        }
+    }
+    /// Returns the URL to redirect the user to, along with the CSRF token to remember.
+    pub fn authorize_url(&self) -> (Url, CsrfToken) {
+        self.client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.provider.scopes())
+            .url()
+    }
+    /// Exchanges an authorization code for an access token.
+    pub async fn exchange_code(&self, http: &reqwest::Client, code: String) -> BResult<String> {
+        let token = self
+            .client
+            .exchange_code(AuthorizationCode::new(code))
+            .request_async(http)
+            .await
+            .map_err(|e| ApiError::OAuthTokenExchangeError(e.to_string()))?;
+        Ok(token.access_token().secret().clone())
+    }
+    /// Fetches the profile of the token's owner and maps it to a [`NewGuest`].
+    pub async fn fetch_identity(
+        &self,
+        http: &reqwest::Client,
+        access_token: &str,
+    ) -> BResult<NewGuest> {
+        let mut request = http
+            .get(self.provider.user_info_url())
+            .header(USER_AGENT.as_str(), "ga-guestbook")
+            .header(ACCEPT.as_str(), "application/json")
+            .header(AUTHORIZATION.as_str(), format!("Bearer {access_token}"));
+        for (name, value) in self.provider.user_info_headers() {
+            request = request.header(name, value);
+        }
+        let info = async {
+                request.send().await?.error_for_status()?.json::<serde_json::Value>().await
+            }
+            .await
+            .map_err(|e| ApiError::OAuthUserFetchError(e.to_string()))?;
+        self.provider.map_identity(info)
+    }
+}
+/// Registry of the providers configured for this deployment.
+#[derive(Debug, Clone, Default)]
+pub struct Providers(HashMap<Provider, ConfiguredProvider>);
+impl Providers {
+    /// Registers every provider that has credentials in `config`.
+    ///
+    /// GitHub is always available; GitLab and OIDC are opt-in.
+    pub fn from_config(config: &AppConfig) -> Self {
+        let domain = config.domain.as_str();
+        let defaults = GitHub::default();
+        let github = GitHub {
+            authorize: config.gabioinf.authorize.clone().unwrap_or(defaults.authorize),
+            token: config.gabioinf.token.clone().unwrap_or(defaults.token),
+            api: config.gabioinf.api.clone().unwrap_or(defaults.api),
+        };
+        let mut providers = Self::default().with(
+            ConfiguredProvider::new(
+                github,
+                config.gabioinf.id.as_str(),
+                config.gabioinf.secret.as_str(),
+                domain,
+            ),
+        );
+        if let Some(gitlab) = &config.providers.gitlab {
+            providers = providers
+                .with(
+                    ConfiguredProvider::new(
+                        GitLab { url: gitlab.url.clone() },
+                        gitlab.id.as_str(),
+                        gitlab.secret.as_str(),
+                        domain,
+                    ),
+                );
+        }
+        if let Some(oidc) = &config.providers.oidc {
+            providers = providers
+                .with(
+                    ConfiguredProvider::new(
+                        Oidc {
+                            authorize: oidc.authorize.clone(),
+                            token: oidc.token.clone(),
+                            userinfo: oidc.userinfo.clone(),
+                            scopes: oidc.scopes.clone(),
+                        },
+                        oidc.id.as_str(),
+                        oidc.secret.as_str(),
+                        domain,
+                    ),
+                );
+        }
+        providers
+    }
+    /// Adds a provider to the registry, replacing any previous one of the same kind.
+    pub fn with(mut self, provider: ConfiguredProvider) -> Self {
+        self.0.insert(provider.kind(), provider);
+        self
+    }
+    /// Looks up a configured provider.
+    ///
+    /// # Returns
+    ///
+    /// The provider, or [`ApiError::NotFoundError`] if it is not configured.
+    pub fn get(&self, kind: Provider) -> BResult<&ConfiguredProvider> {
+        self.0
+            .get(&kind)
+            .ok_or(ApiError::NotFoundError(format!("OAuth provider {kind} is not configured")))
+    }
+    /// Lists the configured providers in a stable order.
+    pub fn kinds(&self) -> Vec<Provider> {
+        [Provider::Github, Provider::Gitlab, Provider::Oidc]
+            .into_iter()
+            .filter(|kind| self.0.contains_key(kind))
+            .collect()
+    }
+    /// Describes the configured providers to the client, in the order of [`Self::kinds`].
+    pub fn login_providers(&self) -> Vec<LoginProvider> {
+        self.kinds().into_iter().map(|kind| self.0[&kind].login_provider()).collect()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    #[test]
+    fn test_github_identity() {
+        let guest = GitHub::default()
+            .map_identity(json!({ "id": 583231, "login": "octocat", "name": null }))
+            .unwrap();
+        assert_eq!(guest.provider, Provider::Github);
+        assert_eq!(guest.external_id, ExternalId::from(583231));
+        assert_eq!(guest.username, "octocat");
+        assert_eq!(guest.name, None);
+    }
+    #[test]
+    fn test_gitlab_identity() {
+        let gitlab = GitLab {
+            url: "https://gitlab.example.com/".to_string(),
+        };
+        assert_eq!(gitlab.user_info_url(), "https://gitlab.example.com/api/v4/user");
+        let guest = gitlab
+            .map_identity(json!({ "id": 1, "username": "john_smith", "name": "John Smith" }))
+            .unwrap();
+        assert_eq!(guest.provider, Provider::Gitlab);
+        assert_eq!(guest.external_id, ExternalId::from(1));
+        assert_eq!(guest.name.as_deref(), Some("John Smith"));
+        let login = ConfiguredProvider::new(gitlab, "id", "secret", "localhost:8080")
+            .login_provider();
+        assert_eq!(
+            login.profile_url("john_smith").as_deref(),
+            Some("https://gitlab.example.com/john_smith")
+        );
+    }
+    #[test]
+    fn test_oidc_identity() {
+        let oidc = Oidc {
+            authorize: "https://id.example.com/authorize".to_string(),
+            token: "https://id.example.com/token".to_string(),
+            userinfo: "https://id.example.com/userinfo".to_string(),
+            scopes: vec!["openid".to_string(), "profile".to_string()],
+        };
+        let guest = oidc
+            .map_identity(json!({ "sub": "248289761001", "preferred_username": "jane" }))
+            .unwrap();
+        assert_eq!(guest.external_id, ExternalId::from("248289761001".to_string()));
+        assert_eq!(guest.username, "jane");
+        let guest = oidc.map_identity(json!({ "sub": "248289761001" })).unwrap();
+        assert_eq!(guest.username, "248289761001");
+        assert!(oidc.map_identity(json!({ "preferred_username": "jane" })).is_err());
+    }
+    #[test]
+    fn test_unconfigured_provider() {
+        let providers = Providers::default()
+            .with(
+                ConfiguredProvider::new(GitHub::default(), "id", "secret", "localhost:8080"),
+            );
+        assert_eq!(providers.kinds(), vec![Provider::Github]);
+        assert!(providers.get(Provider::Github).is_ok());
+        assert!(matches!(providers.get(Provider::Gitlab), Err(ApiError::NotFoundError(_))));
+    }
+}
@@ -0,0 +1,140 @@
+//! Validation of post-login redirect targets.
+//!
+//! The `next` parameter of `/v1/login` is user controlled, so it is only honoured if it
+//! is a same-origin relative path or points at an explicitly allowed origin.
+use reqwest::Url;
+/// Where to send users when `next` is missing or rejected.
+pub const DEFAULT_REDIRECT: &str = "/";
+/// Returns `next` if it is a safe redirect target, [`DEFAULT_REDIRECT`] otherwise.
+///
+/// # Arguments
+///
+/// * `next` - The requested redirect target
+/// * `allowed` - Origins, e.g. `https://gabioinf.com`, that absolute targets may point at
+pub fn resolve_next(next: Option<&str>, allowed: &[String]) -> String {
+    next.filter(|next| is_safe_redirect(next, allowed))
+        .unwrap_or(DEFAULT_REDIRECT)
+        .to_string()
+}
+/// Checks whether `next` is a same-origin relative path or an allowed absolute URL.
+pub fn is_safe_redirect(next: &str, allowed: &[String]) -> bool {
+    if next.is_empty() || next.len() > 2048 {
+        return false;
+    }
+    // Browsers strip tabs and newlines and treat backslashes like slashes, so those
+    // are rejected outright, in every decoding layer.
+    let decoded = decode_fully(next);
+    if next.chars().any(|c| c.is_whitespace())
+        || [next, decoded.as_str()]
+            .iter()
+            .any(|s| s.chars().any(|c| c.is_control() || c == '\\'))
+    {
+        return false;
+    }
+    if next.starts_with('/') {
+        return !next.starts_with("//") && !decoded.starts_with("//");
+    }
+    is_allowed_origin(next, allowed)
+}
+fn is_allowed_origin(next: &str, allowed: &[String]) -> bool {
+    let Ok(url) = Url::parse(next) else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") || !url.username().is_empty()
+        || url.password().is_some()
+    {
+        return false;
+    }
+    let origin = url.origin().ascii_serialization();
+    allowed
+        .iter()
+        .filter_map(|entry| Url::parse(entry).ok())
+        .any(|entry| entry.origin().ascii_serialization() == origin)
+}
+/// Percent-decodes `input` until it stops changing, to see through nested encodings.
+fn decode_fully(input: &str) -> String {
+    let mut current = input.to_string();
+    for _ in 0..4 {
+        let decoded = percent_decode(&current);
+        if decoded == current {
+            break;
+        }
+        current = decoded;
+    }
+    current
+}
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn allowed() -> Vec<String> {
+        vec!["https://gabioinf.com".to_string()]
+    }
+    #[test]
+    fn test_relative_paths() {
+        assert_eq!(resolve_next(Some("/guestbook"), &[]), "/guestbook");
+        assert_eq!(resolve_next(Some("/guestbook?page=2#top"), &[]), "/guestbook?page=2#top");
+        assert_eq!(resolve_next(None, &[]), "/");
+        assert_eq!(resolve_next(Some(""), &[]), "/");
+        assert_eq!(resolve_next(Some("guestbook"), &[]), "/");
+    }
+    #[test]
+    fn test_protocol_relative() {
+        for next in ["//evil.com", "///evil.com", "/\\evil.com", "\\\\evil.com", "/\t/evil.com"] {
+            assert!(!is_safe_redirect(next, &allowed()), "{next:?} should be rejected");
+        }
+    }
+    #[test]
+    fn test_absolute() {
+        assert!(is_safe_redirect("https://gabioinf.com/guestbook", &allowed()));
+        for next in [
+            "https://evil.com",
+            "http://gabioinf.com/guestbook",
+            "https://gabioinf.com.evil.com",
+            "https://gabioinf.com@evil.com",
+            "https://user@gabioinf.com",
+            "javascript:alert(1)",
+            "data:text/html,hi",
+        ] {
+            assert!(!is_safe_redirect(next, &allowed()), "{next:?} should be rejected");
+        }
+        assert!(!is_safe_redirect("https://gabioinf.com/guestbook", &[]));
+    }
+    #[test]
+    fn test_encoded() {
+        for next in [
+            "/%2Fevil.com",
+            "/%2F%2Fevil.com",
+            "/%252F/evil.com",
+            "/%5Cevil.com",
+            "/%09/evil.com",
+            "%2F%2Fevil.com",
+            "https:%2F%2Fevil.com",
+        ] {
+            assert!(!is_safe_redirect(next, &allowed()), "{next:?} should be rejected");
+        }
+        assert!(is_safe_redirect("/guestbook?q=a%20b", &allowed()));
+    }
+}
@@ -0,0 +1,78 @@
+//! Tracking of the sessions guests are logged in with.
+//!
+//! Every login is recorded together with the user agent and a coarse client address, so
+//! guests can review where they are signed in and revoke sessions they don't recognise.
+use crate::backend::errors::{ApiError, BResult};
+use crate::backend::extractors::forwarded_ip;
+use crate::backend::repos::SessionRepo;
+use crate::shared::models::GuestId;
+use axum::extract::{Request, State};
+use axum::http::{header::USER_AGENT, HeaderMap};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::net::IpAddr;
+use tower_sessions::Session;
+/// Longest user agent string that is stored.
+const MAX_USER_AGENT_LEN: usize = 256;
+/// Reduces an address to its network, so sessions can be told apart without storing
+/// the precise location of a guest: a `/24` for IPv4 and a `/48` for IPv6.
+pub fn coarse_ip(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            format!("{a}.{b}.{c}.0/24")
+        }
+        IpAddr::V6(ip) => {
+            let [a, b, c, ..] = ip.segments();
+            format!("{a:x}:{b:x}:{c:x}::/48")
+        }
+    }
+}
+/// Records the session that `guest_id` just logged in with.
+///
+/// Must be called after the login, once the session has been given its final ID.
+pub async fn record_login(
+    repo: &SessionRepo,
+    session: &Session,
+    guest_id: GuestId,
+    headers: &HeaderMap,
+) -> BResult<()> {
+    session.save().await.map_err(|e| ApiError::UnhandledError(e.to_string()))?;
+    let Some(session_id) = session.id() else {
+        return Ok(());
+    };
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(MAX_USER_AGENT_LEN).collect::<String>());
+    let ip = forwarded_ip(headers).map(coarse_ip);
+    repo.record(&session_id.to_string(), guest_id, user_agent.as_deref(), ip.as_deref())
+        .await
+}
+/// Middleware updating the last activity of the session a request was made with.
+pub async fn track_activity(
+    State(repo): State<SessionRepo>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    if let Some(session_id) = session.id() {
+        if let Err(e) = repo.touch(&session_id.to_string()).await {
+            dioxus_logger::tracing::warn!(error = %e, "Failed to update session activity");
+        }
+    }
+    response
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_coarse_ip() {
+        assert_eq!(coarse_ip("203.0.113.57".parse().unwrap()), "203.0.113.0/24");
+        assert_eq!(
+            coarse_ip("2001:db8:85a3:8d3:1319:8a2e:370:7348".parse().unwrap()),
+            "2001:db8:85a3::/48"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Serves the signatures of guestbook entries as standalone SVG images.
+//!
+//! Signatures stored as strokes are rendered to vector paths. Older signatures that were
+//! stored as PNGs are embedded in an SVG of their size, so every signature is served
+//! under the same URL.
+use crate::backend::errors::{ApiError, BResult};
+use crate::backend::AppState;
+use crate::shared::models::{GuestbookEntry, GuestbookId, SignatureStrokes};
+use crate::shared::stroke::signature_svg;
+use axum::extract::{Path, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use base64::Engine;
+/// Caches must revalidate signatures against their entity tag, so a hidden or deleted
+/// signature stops being served right away.
+const CACHE_POLICY: &str = "public, no-cache";
+/// Lets other sites embed signatures, while the SVG itself may load nothing but the PNG
+/// data of legacy signatures.
+const EMBED_HEADERS: [(&str, &str); 3] = [
+    ("cross-origin-resource-policy", "cross-origin"),
+    ("content-security-policy", "default-src 'none'; img-src data:"),
+    ("x-content-type-options", "nosniff"),
+];
+pub fn router() -> Router<AppState> {
+    Router::new().route("/signatures/{file}", get(self::get::signature))
+}
+/// Renders the signature of an entry as a standalone SVG document.
+///
+/// # Returns
+///
+/// `None` if the entry has no signature or it cannot be decoded.
+pub fn render_signature(entry: &GuestbookEntry) -> Option<String> {
+    let signature = entry.signature.as_deref().filter(|s| !s.is_empty())?;
+    if SignatureStrokes::is_strokes(signature) {
+        return SignatureStrokes::decode(signature).ok().map(|strokes| signature_svg(&strokes));
+    }
+    let png = base64::engine::general_purpose::STANDARD.decode(signature).ok()?;
+    let (width, height) = png_dimensions(&png)?;
+    Some(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}"><image width="{width}" height="{height}" href="data:image/png;base64,{signature}"/></svg>"#
+    ))
+}
+/// Reads the dimensions from the header of a PNG image.
+fn png_dimensions(png: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if png.len() < 24 || !png.starts_with(SIGNATURE) || &png[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(png[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(png[20..24].try_into().ok()?);
+    Some((width, height))
+}
+/// The entity tag of a signature, which changes whenever the entry is edited.
+fn entity_tag(entry: &GuestbookEntry) -> String {
+    format!(
+        "\"{}-{}\"",
+        entry.id.as_value(),
+        entry.updated_at.unix_timestamp_nanos()
+    )
+}
+mod get {
+    use super::*;
+    pub async fn signature(
+        State(state): State<AppState>,
+        Path(file): Path<String>,
+        headers: HeaderMap,
+    ) -> BResult<Response> {
+        let not_found = || ApiError::NotFoundError(format!("Signature {file} not found"));
+        let id = file
+            .strip_suffix(".svg")
+            .and_then(|id| id.parse::<i64>().ok())
+            .map(GuestbookId)
+            .ok_or_else(not_found)?;
+        // Only signatures shown publicly in the guestbook are served.
+        let entry = match state.guestbook_repo.read_visible(id, None).await {
+            Ok(entry) => entry,
+            Err(ApiError::DatabaseError(sqlx::Error::RowNotFound)) => return Err(not_found()),
+            Err(e) => return Err(e),
+        };
+        let etag = entity_tag(&entry);
+        let cache_headers = [
+            (CACHE_CONTROL, CACHE_POLICY.to_string()),
+            (ETAG, etag.clone()),
+        ];
+        let matches = headers
+            .get(IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+        if matches {
+            return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+        }
+        let svg = render_signature(&entry).ok_or_else(not_found)?;
+        let content_type = [(CONTENT_TYPE, "image/svg+xml")];
+        Ok((cache_headers, EMBED_HEADERS, content_type, svg).into_response())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_render_legacy_png() {
+        let header = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x01\x2c\x00\x00\x00\x96";
+        let entry = GuestbookEntry {
+            signature: Some(base64::engine::general_purpose::STANDARD.encode(header)),
+            ..Default::default()
+        };
+        let svg = render_signature(&entry).unwrap();
+        assert!(svg.contains(r#"viewBox="0 0 300 150""#));
+        assert!(render_signature(&GuestbookEntry::default()).is_none());
+        let garbage = GuestbookEntry {
+            signature: Some("bm90IGEgcG5n".to_string()),
+            ..Default::default()
+        };
+        assert!(render_signature(&garbage).is_none());
+    }
+}
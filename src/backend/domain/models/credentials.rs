@@ -1,3 +1,4 @@
+use crate::shared::models::Provider;
 use oauth2::CsrfToken;
 use serde::Deserialize;
 /// Represents the credentials received during the OAuth2 authorization code flow.
@@ -6,6 +7,8 @@ use serde::Deserialize;
 /// It includes the authorization code and CSRF tokens for security verification.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Credentials {
+    /// The provider the authorization flow was started with.
+    pub provider: Provider,
     /// The authorization code received from the OAuth2 server.
     ///
     /// This code is typically exchanged for an access token in the next step of the OAuth2 flow.
@@ -1,10 +1,11 @@
+use super::PermissionTargets;
 use serde::Deserialize;
 /// Represents the different types of user groups in the system.
 ///
 /// This enum is used both in the application logic and as a database type,
 /// corresponding to the 'groupvariant' type in the database.
-#[derive(Debug, Clone, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "groupvariant", rename_all = "lowercase")]
+#[derive(Debug, Clone, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "groupvariant", rename_all = "snake_case")]
 pub enum Group {
     /// Administrators with full system access.
     Admins,
@@ -13,6 +14,33 @@ pub enum Group {
     /// Users who have been flagged for inappropriate behavior.
     NaughtyGuests,
 }
+impl Group {
+    /// The name of the group as shown to users, e.g. in data exports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Group::Admins => "admins",
+            Group::Guests => "guests",
+            Group::NaughtyGuests => "naughty_guests",
+        }
+    }
+    /// Permissions revoked from members of this group.
+    ///
+    /// Denials override grants: a permission listed here is removed even if the user
+    /// holds it through another group or directly.
+    pub fn denied_permissions(&self) -> &'static [PermissionTargets] {
+        match self {
+            Group::NaughtyGuests => {
+                &[
+                    PermissionTargets::AddSignature,
+                    PermissionTargets::EditOwnSignature,
+                    PermissionTargets::React,
+                    PermissionTargets::ReportSignature,
+                ]
+            }
+            Group::Admins | Group::Guests => &[],
+        }
+    }
+}
 /// Represents a group entry as stored in the database.
 ///
 /// This struct combines the group's unique identifier and its type.
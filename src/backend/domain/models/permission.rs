@@ -22,6 +22,33 @@ pub enum PermissionTargets {
     ProDemoteUser,
     /// Permission to edit a user's permissions.
     EditUserPermissions,
+    /// Permission to react to guestbook entries.
+    React,
+    /// Permission to reply to guestbook entries as the site owner.
+    ReplyToSignature,
+    /// Permission to pin guestbook entries to the top of the guestbook.
+    PinSignature,
+    /// Permission to report guestbook entries to the moderators.
+    ReportSignature,
+}
+impl PermissionTargets {
+    /// The name of the permission as shown to users, e.g. in data exports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PermissionTargets::AddSignature => "add_signature",
+            PermissionTargets::DeleteOwnSignature => "delete_own_signature",
+            PermissionTargets::DeleteAnySignature => "delete_any_signature",
+            PermissionTargets::EditOwnSignature => "edit_own_signature",
+            PermissionTargets::MarkAsNaughty => "mark_as_naughty",
+            PermissionTargets::DeleteUser => "delete_user",
+            PermissionTargets::ProDemoteUser => "pro_demote_user",
+            PermissionTargets::EditUserPermissions => "edit_user_permissions",
+            PermissionTargets::React => "react",
+            PermissionTargets::ReplyToSignature => "reply_to_signature",
+            PermissionTargets::PinSignature => "pin_signature",
+            PermissionTargets::ReportSignature => "report_signature",
+        }
+    }
 }
 /// Represents a permission entry as stored in the database.
 ///
@@ -6,6 +6,7 @@
 //! straitforwardly converted into an axum response.
 //!
 //! [`IntoResponse`]: axum::response::IntoResponse
+use crate::shared::models::AuthErrorReason;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use thiserror::Error;
@@ -35,6 +36,22 @@ pub enum ApiError {
     /// Represents errors from external services
     #[error("External service error: {0}")]
     ExternalServiceError(String),
+    /// Represents an error reported by the OAuth provider on the callback, e.g. when
+    /// the user denies access
+    #[error("OAuth provider error: {error}")]
+    OAuthProviderError {
+        error: String,
+        description: Option<String>,
+    },
+    /// Represents a missing or mismatching OAuth CSRF state
+    #[error("OAuth state mismatch")]
+    OAuthStateMismatch,
+    /// Represents a failure to exchange the authorization code for a token
+    #[error("OAuth token exchange failed: {0}")]
+    OAuthTokenExchangeError(String),
+    /// Represents a failure to fetch the user's profile from the OAuth provider
+    #[error("OAuth user fetch failed: {0}")]
+    OAuthUserFetchError(String),
     /// Represents internal server errors
     #[error("Internal server error")]
     InternalServerError,
@@ -47,50 +64,64 @@ pub enum ApiError {
     #[error("Unhandled error: {0}")]
     UnhandledError(String),
 }
+impl ApiError {
+    /// The HTTP status code this error is reported with.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::DatabaseError(_)
+            | Self::InternalServerError
+            | Self::UnhandledError(_)
+            | Self::FromRequestPartsError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::AuthenticationError(_) => StatusCode::UNAUTHORIZED,
+            Self::AuthorizationError(_) => StatusCode::FORBIDDEN,
+            Self::NotFoundError(_) => StatusCode::NOT_FOUND,
+            Self::AlreadyExistsError(_) => StatusCode::CONFLICT,
+            Self::ValidationError(_)
+            | Self::OAuthProviderError { .. }
+            | Self::OAuthStateMismatch => StatusCode::BAD_REQUEST,
+            Self::ExternalServiceError(_)
+            | Self::OAuthTokenExchangeError(_)
+            | Self::OAuthUserFetchError(_) => StatusCode::BAD_GATEWAY,
+            Self::NotImplementedErrpr(_) => StatusCode::NOT_IMPLEMENTED,
+        }
+    }
+    /// The reason shown to a user whose sign-in failed with this error.
+    pub fn auth_error_reason(&self) -> AuthErrorReason {
+        match self {
+            Self::OAuthProviderError { error, .. } if error == "access_denied" => {
+                AuthErrorReason::Denied
+            }
+            Self::OAuthProviderError { .. } => AuthErrorReason::Provider,
+            Self::OAuthStateMismatch => AuthErrorReason::State,
+            Self::OAuthTokenExchangeError(_) => AuthErrorReason::Token,
+            Self::OAuthUserFetchError(_) => AuthErrorReason::Profile,
+            _ => AuthErrorReason::Internal,
+        }
+    }
+}
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let response = match self {
-            Self::DatabaseError(e) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, format!("DatabaseError: {e}"))
-            }
-            Self::AuthenticationError(e) => {
-                (StatusCode::UNAUTHORIZED, format!("AuthenticationError: {e}"))
-            }
-            Self::AuthorizationError(e) => {
-                (StatusCode::FORBIDDEN, format!("AuthorizationError: {e}"))
-            }
-            Self::NotFoundError(e) => {
-                (StatusCode::NOT_FOUND, format!("NotFoundError: {e}"))
-            }
-            Self::AlreadyExistsError(e) => {
-                (StatusCode::CONFLICT, format!("AlreadyExistsError: {e}"))
-            }
-            Self::ValidationError(e) => {
-                (StatusCode::BAD_REQUEST, format!("ValidationError: {e}"))
-            }
-            Self::ExternalServiceError(e) => {
-                (StatusCode::BAD_GATEWAY, format!("ExternalServiceError: {e}"))
-            }
+        let status = self.status_code();
+        let message = match self {
+            Self::DatabaseError(e) => format!("DatabaseError: {e}"),
+            Self::AuthenticationError(e) => format!("AuthenticationError: {e}"),
+            Self::AuthorizationError(e) => format!("AuthorizationError: {e}"),
+            Self::NotFoundError(e) => format!("NotFoundError: {e}"),
+            Self::AlreadyExistsError(e) => format!("AlreadyExistsError: {e}"),
+            Self::ValidationError(e) => format!("ValidationError: {e}"),
+            Self::ExternalServiceError(e) => format!("ExternalServiceError: {e}"),
+            Self::OAuthProviderError { error, .. } => format!("OAuthProviderError: {error}"),
+            Self::OAuthStateMismatch => "OAuthStateMismatch: OAuth state mismatch".to_string(),
+            Self::OAuthTokenExchangeError(e) => format!("OAuthTokenExchangeError: {e}"),
+            Self::OAuthUserFetchError(e) => format!("OAuthUserFetchError: {e}"),
             Self::InternalServerError => {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "InternalServerError: Internal server error".to_string(),
-                )
-            }
-            Self::UnhandledError(e) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, format!("UnhandledError: {e}"))
-            }
-            Self::NotImplementedErrpr(e) => {
-                (StatusCode::NOT_IMPLEMENTED, format!("NotImplementedError: {e}"))
-            }
-            Self::FromRequestPartsError(e) => {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("FromRequestPartsError: {e}"),
-                )
+                "InternalServerError: Internal server error".to_string()
             }
+            Self::UnhandledError(e) => format!("UnhandledError: {e}"),
+            Self::NotImplementedErrpr(e) => format!("NotImplementedError: {e}"),
+            Self::FromRequestPartsError(e) => format!("FromRequestPartsError: {e}"),
         };
-        response.into_response()
+        (status, message).into_response()
     }
 }
 /// Implements conversion from [`reqwest::Error`] to [`ApiError`]
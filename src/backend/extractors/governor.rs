@@ -38,10 +38,7 @@ impl KeyExtractor for CookieExtractor {
         jar.get("id")
             .map(|cookie| cookie.value().to_string())
             .or_else(|| {
-                let headers = req.headers();
-                maybe_x_forwarded_for(headers)
-                    .or_else(|| maybe_x_real_ip(headers))
-                    .or_else(|| maybe_forwarded(headers))
+                forwarded_ip(req.headers())
                     .or_else(|| PeerIpKeyExtractor.extract(req).ok())
                     .map(|ip| ip.to_string())
             })
@@ -56,6 +53,14 @@ impl KeyExtractor for CookieExtractor {
         "CookieExtractor"
     }
 }
+/// Returns the client IP address reported by a reverse proxy, if any.
+///
+/// Checks `x-forwarded-for`, `x-real-ip` and `forwarded`, in that order.
+pub fn forwarded_ip(headers: &HeaderMap) -> Option<IpAddr> {
+    maybe_x_forwarded_for(headers)
+        .or_else(|| maybe_x_real_ip(headers))
+        .or_else(|| maybe_forwarded(headers))
+}
 /// Tries to parse the `x-forwarded-for` header
 fn maybe_x_forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
     headers
@@ -30,7 +30,14 @@ pub mod extractors;
 pub mod repos;
 /// Server
 pub mod server;
+/// Cookie signing keys and rotation
+pub mod session_keys;
+/// Encryption of access tokens at rest
+pub mod token_cipher;
 /// Utilities
 pub mod utils;
 /// Web API implementation
 pub mod wapi;
+/// Integration tests exercising the HTTP API end to end
+#[cfg(test)]
+mod tests;
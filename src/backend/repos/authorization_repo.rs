@@ -31,16 +31,66 @@ impl GroupsAndPermissionsRepo {
         user_id: GuestId,
         group: Group,
     ) -> BResult<()> {
+        Self::add_user_to_group_with(&self.pool, user_id, group).await
+    }
+    /// Adds a user to a specified group using the given executor.
+    ///
+    /// This allows the insert to take part in a larger transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The executor (pool, connection or transaction) to run the query on.
+    /// * `user_id` - The ID of the user to add to the group.
+    /// * `group` - The group to which the user will be added.
+    ///
+    /// # Returns
+    ///
+    /// A `BResult<()>` indicating success or failure.
+    pub async fn add_user_to_group_with<'e, E>(
+        executor: E,
+        user_id: GuestId,
+        group: Group,
+    ) -> BResult<()>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query!(
             "INSERT INTO guests_groups (guest_id, group_id)
              SELECT $1, id FROM groups WHERE name = $2
              ON CONFLICT DO NOTHING",
             user_id.as_value(), group as Group
         )
-            .execute(&self.pool)
+            .execute(executor)
             .await?;
         Ok(())
     }
+    /// Records that a bootstrap admin has been promoted, using the given executor.
+    ///
+    /// Bootstrap admins are promoted at most once, so that demoting them sticks.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The executor (pool, connection or transaction) to run the query on.
+    /// * `user_id` - The ID of the bootstrap admin.
+    ///
+    /// # Returns
+    ///
+    /// A `BResult<bool>` that is `true` if the user had not been promoted before.
+    pub async fn record_bootstrap_promotion_with<'e, E>(
+        executor: E,
+        user_id: GuestId,
+    ) -> BResult<bool>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let inserted = sqlx::query!(
+            "INSERT INTO bootstrap_promotions (guest_id) VALUES ($1) ON CONFLICT DO NOTHING",
+            user_id.as_value()
+        )
+            .execute(executor)
+            .await?;
+        Ok(inserted.rows_affected() == 1)
+    }
     /// Removes a user from a specified group.
     ///
     /// # Arguments
@@ -139,6 +189,53 @@ impl GroupsAndPermissionsRepo {
             .collect();
         Ok(groups)
     }
+    /// Retrieves the IDs of all users belonging to a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The group whose members are to be retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A `BResult<Vec<GuestId>>` containing the IDs of the group members.
+    pub async fn get_group_members(&self, group: Group) -> BResult<Vec<GuestId>> {
+        let members = sqlx::query_scalar!(
+            "SELECT gg.guest_id
+             FROM guests_groups gg
+             JOIN groups g ON g.id = gg.group_id
+             WHERE g.name = $1",
+            group as Group
+        )
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(GuestId)
+            .collect();
+        Ok(members)
+    }
+    /// Adds a user to or removes a user from a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user whose membership is changed.
+    /// * `group` - The group in question.
+    /// * `member` - Whether the user should belong to the group.
+    ///
+    /// # Returns
+    ///
+    /// A `BResult<()>` indicating success or failure.
+    pub async fn set_group_membership(
+        &self,
+        user_id: GuestId,
+        group: Group,
+        member: bool,
+    ) -> BResult<()> {
+        if member {
+            self.add_user_to_group(user_id, group).await
+        } else {
+            self.remove_user_from_group(user_id, group).await
+        }
+    }
     /// Retrieves permissions specifically assigned to a user.
     ///
     /// # Arguments
@@ -198,6 +295,9 @@ impl GroupsAndPermissionsRepo {
     }
     /// Retrieves all permissions a user has, both specific and group-based.
     ///
+    /// Permissions denied by any of the user's groups (see [`Group::denied_permissions`])
+    /// are removed from the result, regardless of where they were granted.
+    ///
     /// # Arguments
     ///
     /// * `user_id` - The ID of the user whose permissions are to be retrieved.
@@ -220,10 +320,48 @@ impl GroupsAndPermissionsRepo {
             user_id.as_value()
         )
             .fetch_all(&self.pool)
+            .await?;
+        let denied: Vec<PermissionTargets> = self
+            .get_user_groups(user_id)
             .await?
             .iter()
-            .map(|p| p.name.clone())
+            .flat_map(|g| g.denied_permissions().iter().cloned())
+            .collect();
+        let permissions = permissions
+            .into_iter()
+            .map(|p| p.name)
+            .filter(|p| !denied.contains(p))
             .collect();
         Ok(permissions)
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::utils::setup_guest;
+    use sqlx::PgPool;
+    #[sqlx::test]
+    async fn test_group_membership(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let repo = GroupsAndPermissionsRepo::new(pool);
+        repo.set_group_membership(guest.id, Group::NaughtyGuests, true).await.unwrap();
+        let members = repo.get_group_members(Group::NaughtyGuests).await.unwrap();
+        assert_eq!(members, vec![guest.id]);
+        repo.set_group_membership(guest.id, Group::NaughtyGuests, false).await.unwrap();
+        let members = repo.get_group_members(Group::NaughtyGuests).await.unwrap();
+        assert!(members.is_empty());
+    }
+    #[sqlx::test]
+    async fn test_naughty_denies_signing(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let repo = GroupsAndPermissionsRepo::new(pool);
+        repo.add_user_to_group(guest.id, Group::Guests).await.unwrap();
+        repo.add_permission_to_user(guest.id, PermissionTargets::AddSignature).await.unwrap();
+        repo.add_user_to_group(guest.id, Group::NaughtyGuests).await.unwrap();
+        let permissions = repo.get_all_user_permissions(guest.id).await.unwrap();
+        assert!(!permissions.contains(&PermissionTargets::AddSignature));
+        assert!(!permissions.contains(&PermissionTargets::EditOwnSignature));
+        assert!(!permissions.contains(&PermissionTargets::React));
+        assert!(permissions.contains(&PermissionTargets::DeleteOwnSignature));
+    }
+}
@@ -1,10 +1,10 @@
 #![allow(unused)]
-use super::{PgRepository, Repository};
+use super::{PgRepository, Repository, SessionRepo};
 use crate::backend::{
     errors::{ApiError, BResult},
     AppState,
 };
-use crate::shared::models::{GithubId, Guest, GuestId};
+use crate::shared::models::{ExternalId, Guest, GuestId, Provider};
 use axum::{extract::FromRequestParts, http::request::Parts};
 use serde::{Deserialize, Serialize};
 /// Criteria for querying guest data.
@@ -12,8 +12,8 @@ use serde::{Deserialize, Serialize};
 pub enum GuestCriteria {
     /// Query by guest ID.
     WithGuestId(GuestId),
-    /// Query by GitHub ID.
-    WithGithubId(GithubId),
+    /// Query by provider and the guest's ID at that provider.
+    WithIdentity(Provider, ExternalId),
     /// Query for the latest guest.
     Latest,
 }
@@ -22,7 +22,12 @@ impl Repository<Guest> for PgRepository<Guest> {
     type Criteria = GuestCriteria;
     /// Retrieves all guests, ordered by creation date.
     async fn read_all(&self) -> BResult<Vec<Guest>> {
-        let guests = sqlx::query_as!(Guest, "SELECT * FROM guests ORDER BY created_at")
+        let guests = sqlx::query_as!(
+            Guest,
+            r#"SELECT id, provider as "provider: Provider", external_id, name, username, created_at,
+             updated_at, access_token, session_secret
+             FROM guests ORDER BY created_at"#
+        )
             .fetch_all(&self.pool)
             .await?;
         Ok(guests)
@@ -32,65 +37,137 @@ impl Repository<Guest> for PgRepository<Guest> {
         let guest = match criteria {
             GuestCriteria::WithGuestId(id) => {
                 sqlx::query_as!(
-                    Guest, "SELECT * FROM guests WHERE id = $1", id.as_value()
+                    Guest,
+                    r#"SELECT id, provider as "provider: Provider", external_id, name, username, created_at,
+                     updated_at, access_token, session_secret
+                     FROM guests WHERE id = $1"#,
+                    id.as_value()
                 )
                     .fetch_one(&self.pool)
                     .await?
             }
-            GuestCriteria::WithGithubId(github_id) => {
-                sqlx::query_as!(
-                    Guest, "SELECT * FROM guests WHERE github_id = $1", github_id
-                    .as_value()
-                )
-                    .fetch_one(&self.pool)
+            GuestCriteria::WithIdentity(provider, external_id) => {
+                Self::find_by_identity_with(&self.pool, *provider, external_id)
                     .await?
+                    .ok_or(ApiError::NotFoundError("Guest not found".to_string()))?
             }
             GuestCriteria::Latest => {
-                sqlx::query_as!(Guest, "SELECT * FROM guests ORDER BY created_at DESC")
+                sqlx::query_as!(
+                    Guest,
+                    r#"SELECT id, provider as "provider: Provider", external_id, name, username, created_at,
+                     updated_at, access_token, session_secret
+                     FROM guests ORDER BY created_at DESC"#
+                )
                     .fetch_one(&self.pool)
                     .await?
             }
         };
         Ok(guest)
     }
-    /// Creates a new guest or updates an existing one if there's a conflict on github_id.
+    /// Creates a new guest or updates an existing one if there's a conflict on the provider identity.
     async fn create(&self, guest: &Guest) -> BResult<Guest> {
-        let created_guest = sqlx::query_as!(
-            Guest,
-            "INSERT INTO guests (github_id, name, username, access_token) 
-             VALUES ($1, $2, $3, $4) 
-             ON CONFLICT (github_id) DO UPDATE 
-             SET access_token = excluded.access_token 
-             RETURNING *",
-            guest.github_id.as_value(), guest.name, guest.username, guest.access_token
-        )
-            .fetch_one(&self.pool)
-            .await?;
-        Ok(created_guest)
+        Self::upsert_with(&self.pool, guest).await
     }
     /// Updates an existing guest's information.
     async fn update(&self, guest: &Guest) -> BResult<Guest> {
         let updated_guest = sqlx::query_as!(
             Guest,
-            "UPDATE guests
+            r#"UPDATE guests
             SET name = $2, username = $3, updated_at = NOW()
             WHERE id = $1
-            RETURNING *",
+            RETURNING id, provider as "provider: Provider", external_id, name, username, created_at,
+            updated_at, access_token, session_secret"#,
             guest.id.as_value(), guest.name, guest.username,
         )
             .fetch_one(&self.pool)
             .await?;
         Ok(updated_guest)
     }
-    /// Deletes a guest from the database.
+    /// Deletes a guest together with everything referencing them.
+    ///
+    /// The guest's entry, group and permission links and sessions are removed in the
+    /// same transaction, since the foreign keys on them restrict deleting the guest.
     async fn delete(&self, guest: &Guest) -> BResult<()> {
-        sqlx::query!("DELETE FROM guests WHERE id = $1", guest.id.as_value())
+        let id = guest.id.as_value();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!("DELETE FROM guestbook WHERE author_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM guests_groups WHERE guest_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM guests_permissions WHERE guest_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        SessionRepo::revoke_all_with(&mut *tx, guest.id).await?;
+        let deleted = sqlx::query!("DELETE FROM guests WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(ApiError::NotFoundError("Guest not found".to_string()));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+impl PgRepository<Guest> {
+    /// Creates a new guest or updates an existing one if there's a conflict on the provider identity,
+    /// using the given executor.
+    ///
+    /// This allows the upsert to take part in a larger transaction.
+    pub async fn upsert_with<'e, E>(executor: E, guest: &Guest) -> BResult<Guest>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let created_guest = sqlx::query_as!(
+            Guest,
+            r#"INSERT INTO guests (provider, external_id, name, username, access_token) 
+             VALUES ($1, $2, $3, $4, $5) 
+             ON CONFLICT (provider, external_id) DO UPDATE 
+             SET access_token = excluded.access_token 
+             RETURNING id, provider as "provider: Provider", external_id, name, username, created_at,
+             updated_at, access_token, session_secret"#,
+            guest.provider as Provider, guest.external_id.as_value(), guest.name, guest.username,
+            guest.access_token
+        )
+            .fetch_one(executor)
+            .await?;
+        Ok(created_guest)
+    }
+    /// Replaces the guest's session secret with a fresh random value.
+    ///
+    /// Since the secret backs the session auth hash, this invalidates all of the
+    /// guest's existing sessions.
+    pub async fn rotate_session_secret(&self, id: GuestId) -> BResult<()> {
+        sqlx::query!(
+            "UPDATE guests SET session_secret = DEFAULT, updated_at = NOW() WHERE id = $1",
+            id.as_value()
+        )
             .execute(&self.pool)
             .await?;
         Ok(())
     }
+    /// Retrieves a guest by provider identity using the given executor, if it exists.
+    pub async fn find_by_identity_with<'e, E>(
+        executor: E,
+        provider: Provider,
+        external_id: &ExternalId,
+    ) -> BResult<Option<Guest>>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let guest = sqlx::query_as!(
+            Guest,
+            r#"SELECT id, provider as "provider: Provider", external_id, name, username, created_at,
+             updated_at, access_token, session_secret
+             FROM guests WHERE provider = $1 AND external_id = $2"#,
+            provider as Provider, external_id.as_value()
+        )
+            .fetch_optional(executor)
+            .await?;
+        Ok(guest)
+    }
 }
-impl PgRepository<Guest> {}
 impl FromRequestParts<AppState> for Guest {
     type Rejection = ApiError;
     /// Extracts a [`Guest`] instance from the request parts.
@@ -117,26 +194,27 @@ mod tests {
     async fn test_create_and_read_guest(pool: PgPool) {
         let repo = PgRepository::<Guest>::new(pool);
         let guest = Guest {
-            github_id: GithubId(12345),
+            external_id: "12345".to_string().into(),
             name: "Test User".to_string(),
             username: "testuser".to_string(),
             ..Default::default()
         };
         let created_guest = repo.create(&guest).await.unwrap();
-        assert_eq!(created_guest.github_id, guest.github_id);
+        assert_eq!(created_guest.provider, Provider::Github);
+        assert_eq!(created_guest.external_id, guest.external_id);
         assert_eq!(created_guest.name, guest.name);
         let read_guest = repo
-            .read(&GuestCriteria::WithGithubId(guest.github_id))
+            .read(&GuestCriteria::WithIdentity(guest.provider, guest.external_id.clone()))
             .await
             .unwrap();
         assert_eq!(read_guest.id, created_guest.id);
-        assert_eq!(read_guest.github_id, guest.github_id);
+        assert_eq!(read_guest.external_id, guest.external_id);
     }
     #[sqlx::test]
     async fn test_update_guest(pool: PgPool) {
         let repo = PgRepository::<Guest>::new(pool);
         let mut guest = Guest {
-            github_id: GithubId(67890),
+            external_id: "67890".to_string().into(),
             name: "Update Test".to_string(),
             username: "updatetest".to_string(),
             ..Default::default()
@@ -151,14 +229,116 @@ mod tests {
     async fn test_delete_guest(pool: PgPool) {
         let repo = PgRepository::<Guest>::new(pool);
         let guest = Guest {
-            github_id: GithubId(11111),
+            external_id: "11111".to_string().into(),
             name: "Delete Test".to_string(),
             username: "deletetest".to_string(),
             ..Default::default()
         };
         let created_guest = repo.create(&guest).await.unwrap();
         repo.delete(&created_guest).await.unwrap();
-        let result = repo.read(&GuestCriteria::WithGithubId(guest.github_id)).await;
+        let result = repo.read(&GuestCriteria::WithIdentity(guest.provider, guest.external_id.clone())).await;
         assert!(result.is_err());
     }
+    #[sqlx::test]
+    async fn test_delete_guest_with_dependents(pool: PgPool) {
+        use crate::backend::domain::models::{Group, PermissionTargets};
+        use crate::backend::repos::GroupsAndPermissionsRepo;
+        use crate::shared::models::GuestbookEntry;
+        let guest = crate::backend::utils::setup_guest(&pool).await;
+        let entry = GuestbookEntry {
+            message: "Bye".to_string(),
+            author_id: guest.id,
+            author_username: guest.username.clone(),
+            ..Default::default()
+        };
+        PgRepository::<GuestbookEntry>::new(pool.clone()).create(&entry).await.unwrap();
+        let gp_repo = GroupsAndPermissionsRepo::new(pool.clone());
+        gp_repo.add_user_to_group(guest.id, Group::Guests).await.unwrap();
+        gp_repo.add_permission_to_user(guest.id, PermissionTargets::AddSignature).await.unwrap();
+        sqlx::query!(
+            "INSERT INTO tower_sessions.session (id, data, expiry_date)
+            VALUES ('doomed', '', NOW() + INTERVAL '1 day')"
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        SessionRepo::new(pool.clone()).record("doomed", guest.id, None, None).await.unwrap();
+        let repo = PgRepository::<Guest>::new(pool.clone());
+        repo.delete(&guest).await.unwrap();
+        assert!(repo.read(&GuestCriteria::WithGuestId(guest.id)).await.is_err());
+        let remaining = sqlx::query_scalar!(
+            r#"SELECT (SELECT COUNT(*) FROM guestbook)
+                + (SELECT COUNT(*) FROM guests_groups)
+                + (SELECT COUNT(*) FROM guests_permissions)
+                + (SELECT COUNT(*) FROM tower_sessions.session) AS "count!""#
+        )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+        assert!(matches!(repo.delete(&guest).await, Err(ApiError::NotFoundError(_))));
+    }
+    #[sqlx::test]
+    async fn test_identity_is_scoped_to_provider(pool: PgPool) {
+        let repo = PgRepository::<Guest>::new(pool);
+        let github = Guest {
+            provider: Provider::Github,
+            external_id: "33333".to_string().into(),
+            name: "Same Person".to_string(),
+            username: "same".to_string(),
+            ..Default::default()
+        };
+        let gitlab = Guest {
+            provider: Provider::Gitlab,
+            ..github.clone()
+        };
+        let github = repo.create(&github).await.unwrap();
+        let gitlab = repo.create(&gitlab).await.unwrap();
+        assert_ne!(github.id, gitlab.id);
+        let read_guest = repo
+            .read(&GuestCriteria::WithIdentity(Provider::Gitlab, gitlab.external_id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(read_guest.id, gitlab.id);
+    }
+    #[sqlx::test]
+    async fn test_usernames_may_repeat(pool: PgPool) {
+        let repo = PgRepository::<Guest>::new(pool);
+        let first = Guest {
+            provider: Provider::Oidc,
+            external_id: "sub-1".to_string().into(),
+            name: "First".to_string(),
+            username: "taken".to_string(),
+            ..Default::default()
+        };
+        let second = Guest {
+            external_id: "sub-2".to_string().into(),
+            name: "Second".to_string(),
+            ..first.clone()
+        };
+        let first = repo.create(&first).await.unwrap();
+        let second = repo.create(&second).await.unwrap();
+        assert_ne!(first.id, second.id);
+        assert_eq!(first.username, second.username);
+    }
+    #[sqlx::test]
+    async fn test_rotate_session_secret(pool: PgPool) {
+        let repo = PgRepository::<Guest>::new(pool);
+        let guest = Guest {
+            external_id: "22222".to_string().into(),
+            name: "Secret Test".to_string(),
+            username: "secrettest".to_string(),
+            access_token: "encrypted".to_string(),
+            ..Default::default()
+        };
+        let created_guest = repo.create(&guest).await.unwrap();
+        assert_eq!(created_guest.session_secret.len(), 64);
+        repo.rotate_session_secret(created_guest.id).await.unwrap();
+        let rotated_guest = repo
+            .read(&GuestCriteria::WithGuestId(created_guest.id))
+            .await
+            .unwrap();
+        assert_ne!(rotated_guest.session_secret, created_guest.session_secret);
+        assert_eq!(rotated_guest.access_token, created_guest.access_token);
+    }
 }
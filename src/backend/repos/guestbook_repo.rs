@@ -1,8 +1,14 @@
 #![allow(unused)]
 use super::{PgRepository, Repository};
 use crate::backend::errors::{ApiError, BResult};
-use crate::shared::models::{GuestId, GuestbookEntry, GuestbookId};
+use crate::shared::models::{
+    Cursor, GuestId, GuestbookEntry, GuestbookId, Page, Provider, SignatureSearch,
+};
 use serde::{Deserialize, Serialize};
+/// The maximum number of entries that can be pinned at the same time.
+pub const MAX_PINNED_ENTRIES: i64 = 3;
+/// Key of the advisory lock that serializes pinning entries.
+const PIN_LOCK_KEY: i64 = 0x7069_6e6e_6564;
 /// Criteria for querying guestbook entries.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum GuestbookEntryCriteria {
@@ -19,7 +25,10 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
     /// Retrieves all guestbook entries, ordered by creation date descending.
     async fn read_all(&self) -> BResult<Vec<GuestbookEntry>> {
         let entries = sqlx::query_as!(
-            GuestbookEntry, "SELECT * FROM guestbook ORDER BY created_at DESC"
+            GuestbookEntry,
+            r#"SELECT id, message, signature, created_at, updated_at, author_id, author_username,
+             author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
+             FROM guestbook ORDER BY created_at DESC"#
         )
             .fetch_all(&self.pool)
             .await?;
@@ -30,15 +39,21 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
         let entry = match criteria {
             GuestbookEntryCriteria::WithId(id) => {
                 sqlx::query_as!(
-                    GuestbookEntry, "SELECT * FROM guestbook WHERE id = $1", id
-                    .as_value()
+                    GuestbookEntry,
+                    r#"SELECT id, message, signature, created_at, updated_at, author_id, author_username,
+                     author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
+                     FROM guestbook WHERE id = $1"#,
+                    id.as_value()
                 )
                     .fetch_one(&self.pool)
                     .await?
             }
             GuestbookEntryCriteria::WithAuthorId(author_id) => {
                 sqlx::query_as!(
-                    GuestbookEntry, "SELECT * FROM guestbook WHERE author_id = $1",
+                    GuestbookEntry,
+                    r#"SELECT id, message, signature, created_at, updated_at, author_id, author_username,
+                     author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
+                     FROM guestbook WHERE author_id = $1"#,
                     author_id.as_value()
                 )
                     .fetch_one(&self.pool)
@@ -46,7 +61,10 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
             }
             GuestbookEntryCriteria::Latest => {
                 sqlx::query_as!(
-                    GuestbookEntry, "SELECT * FROM guestbook ORDER BY created_at DESC"
+                    GuestbookEntry,
+                    r#"SELECT id, message, signature, created_at, updated_at, author_id, author_username,
+                     author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
+                     FROM guestbook ORDER BY created_at DESC"#
                 )
                     .fetch_one(&self.pool)
                     .await?
@@ -59,12 +77,13 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
         let created_entry = sqlx::query_as!(
             GuestbookEntry,
             r#"
-            INSERT INTO guestbook (message, signature, author_id, author_username)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
+            INSERT INTO guestbook (message, signature, author_id, author_username, author_provider)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, message, signature, created_at, updated_at, author_id, author_username,
+              author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
             "#,
             entry.message, entry.signature, entry.author_id.as_value(), entry
-            .author_username,
+            .author_username, entry.author_provider as Provider,
         )
             .fetch_one(&self.pool)
             .await?;
@@ -78,7 +97,8 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
             UPDATE guestbook
             SET message = $2, signature = $3, updated_at = NOW()
             WHERE id = $1
-            RETURNING *
+            RETURNING id, message, signature, created_at, updated_at, author_id, author_username,
+              author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
             "#,
             entry.id.as_value(), entry.message, entry.signature,
         )
@@ -95,19 +115,209 @@ impl Repository<GuestbookEntry> for PgRepository<GuestbookEntry> {
     }
 }
 impl PgRepository<GuestbookEntry> {
+    /// Retrieves the page of entries following `after`, pinned entries first, then newest
+    /// first.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The cursor returned with the previous page, or `None` for the first page
+    /// * `per_page` - The maximum number of entries on the page
     pub async fn read_page(
         &self,
-        page: u32,
+        after: Option<Cursor>,
         per_page: usize,
-    ) -> BResult<Vec<GuestbookEntry>> {
+    ) -> BResult<Page<GuestbookEntry>> {
         let entries = sqlx::query_as!(
             GuestbookEntry,
-            "SELECT * FROM guestbook ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-            per_page as i64, (page - 1) as i64 * per_page as i64
+            r#"SELECT id, message, signature, created_at, updated_at, author_id, author_username,
+             author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at
+             FROM guestbook
+             WHERE $1::timestamptz IS NULL
+                OR (COALESCE(pinned_at, '-infinity'), created_at, id)
+                   < (COALESCE($4::timestamptz, '-infinity'), $1, $2)
+             ORDER BY COALESCE(pinned_at, '-infinity') DESC, created_at DESC, id DESC
+             LIMIT $3"#,
+            after.map(|c| c.created_at), after.map(|c| c.id.as_value()), per_page as i64 + 1,
+            after.and_then(|c| c.pinned_at)
         )
             .fetch_all(&self.pool)
             .await?;
-        Ok(entries)
+        Ok(Page::from_overfetch(entries, per_page, GuestbookEntry::cursor))
+    }
+    /// Retrieves the page of entries following `after`, hiding those written by naughty
+    /// guests and those hidden after being reported.
+    ///
+    /// Entries by `viewer` are always included, so guests still see their own.
+    pub async fn read_visible_page(
+        &self,
+        after: Option<Cursor>,
+        per_page: usize,
+        viewer: Option<GuestId>,
+    ) -> BResult<Page<GuestbookEntry>> {
+        let entries = sqlx::query_as!(
+            GuestbookEntry,
+            r#"SELECT gb.id, gb.message, gb.signature, gb.created_at, gb.updated_at, gb.author_id,
+             gb.author_username, gb.author_provider as "author_provider: Provider", gb.reply,
+             gb.replied_at, gb.pinned_at, gb.hidden_at
+             FROM guestbook gb
+             WHERE ($1::timestamptz IS NULL
+                OR (COALESCE(gb.pinned_at, '-infinity'), gb.created_at, gb.id)
+                   < (COALESCE($5::timestamptz, '-infinity'), $1, $2))
+             AND (gb.author_id = $4 OR (gb.hidden_at IS NULL AND NOT EXISTS (
+                 SELECT 1 FROM guests_groups gg
+                 JOIN groups g ON g.id = gg.group_id
+                 WHERE gg.guest_id = gb.author_id AND g.name = 'naughty_guests'
+             )))
+             ORDER BY COALESCE(gb.pinned_at, '-infinity') DESC, gb.created_at DESC, gb.id DESC
+             LIMIT $3"#,
+            after.map(|c| c.created_at), after.map(|c| c.id.as_value()), per_page as i64 + 1,
+            viewer.map(|v| v.as_value()), after.and_then(|c| c.pinned_at)
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(Page::from_overfetch(entries, per_page, GuestbookEntry::cursor))
+    }
+    /// Retrieves an entry if it is shown in the public listing, see
+    /// [`read_visible_page`](Self::read_visible_page).
+    ///
+    /// Entries by `viewer` are always returned. Other entries that are hidden or written
+    /// by naughty guests are reported as missing.
+    pub async fn read_visible(
+        &self,
+        id: GuestbookId,
+        viewer: Option<GuestId>,
+    ) -> BResult<GuestbookEntry> {
+        let entry = sqlx::query_as!(
+            GuestbookEntry,
+            r#"SELECT gb.id, gb.message, gb.signature, gb.created_at, gb.updated_at, gb.author_id,
+             gb.author_username, gb.author_provider as "author_provider: Provider", gb.reply,
+             gb.replied_at, gb.pinned_at, gb.hidden_at
+             FROM guestbook gb
+             WHERE gb.id = $1
+             AND (gb.author_id = $2 OR (gb.hidden_at IS NULL AND NOT EXISTS (
+                 SELECT 1 FROM guests_groups gg
+                 JOIN groups g ON g.id = gg.group_id
+                 WHERE gg.guest_id = gb.author_id AND g.name = 'naughty_guests'
+             )))"#,
+            id.as_value(), viewer.map(|v| v.as_value())
+        )
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(entry)
+    }
+    /// Sets or, given `None`, removes the owner's reply to an entry.
+    ///
+    /// Replying does not count as an edit of the entry, so `updated_at` is left alone.
+    pub async fn set_reply(
+        &self,
+        id: GuestbookId,
+        reply: Option<&str>,
+    ) -> BResult<GuestbookEntry> {
+        let entry = sqlx::query_as!(
+            GuestbookEntry,
+            r#"UPDATE guestbook
+             SET reply = $2, replied_at = CASE WHEN $2::text IS NULL THEN NULL ELSE NOW() END
+             WHERE id = $1
+             RETURNING id, message, signature, created_at, updated_at, author_id, author_username,
+             author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at"#,
+            id.as_value(), reply
+        )
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(entry)
+    }
+    /// Pins an entry to the top of the guestbook or unpins it.
+    ///
+    /// Pinning an entry that already is pinned keeps its original pin time. At most
+    /// [`MAX_PINNED_ENTRIES`] visible entries can be pinned at once; pinned entries hidden
+    /// after being reported don't count towards the limit.
+    pub async fn set_pinned(&self, id: GuestbookId, pinned: bool) -> BResult<GuestbookEntry> {
+        let mut tx = self.pool.begin().await?;
+        // Concurrent pins could otherwise all count the same pinned entries and exceed
+        // the limit together.
+        sqlx::query!("SELECT pg_advisory_xact_lock($1)", PIN_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+        let entry = sqlx::query_as!(
+            GuestbookEntry,
+            r#"UPDATE guestbook
+             SET pinned_at = CASE WHEN $2 THEN COALESCE(pinned_at, NOW()) ELSE NULL END
+             WHERE id = $1 AND (NOT $2 OR pinned_at IS NOT NULL
+                 OR (SELECT COUNT(*) FROM guestbook
+                     WHERE pinned_at IS NOT NULL AND hidden_at IS NULL) < $3)
+             RETURNING id, message, signature, created_at, updated_at, author_id, author_username,
+             author_provider as "author_provider: Provider", reply, replied_at, pinned_at, hidden_at"#,
+            id.as_value(), pinned, MAX_PINNED_ENTRIES
+        )
+            .fetch_optional(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        if let Some(entry) = entry {
+            return Ok(entry);
+        }
+        match self.read(&GuestbookEntryCriteria::WithId(id)).await {
+            Ok(_) => Err(ApiError::ValidationError(format!(
+                "At most {MAX_PINNED_ENTRIES} entries can be pinned"
+            ))),
+            Err(ApiError::DatabaseError(sqlx::Error::RowNotFound)) => {
+                Err(ApiError::NotFoundError("Entry not found".to_string()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+    /// Retrieves the page of entries following `after` that match `search`.
+    ///
+    /// Results are ordered like the unfiltered listing rather than by relevance, so they
+    /// can be paged through with the same cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The filters entries must match
+    /// * `after` - The cursor returned with the previous page, or `None` for the first page
+    /// * `per_page` - The maximum number of entries on the page
+    /// * `viewer` - The guest searching, whose own entries are always included
+    /// * `include_moderated` - Whether to include entries written by naughty guests and those
+    ///   hidden after being reported
+    pub async fn search_page(
+        &self,
+        search: &SignatureSearch,
+        after: Option<Cursor>,
+        per_page: usize,
+        viewer: Option<GuestId>,
+        include_moderated: bool,
+    ) -> BResult<Page<GuestbookEntry>> {
+        let text = Some(search.text.trim()).filter(|t| !t.is_empty());
+        let author = Some(search.author.trim()).filter(|a| !a.is_empty());
+        let from = search.from.map(|d| d.midnight().assume_utc());
+        let until = search.to.and_then(|d| d.next_day()).map(|d| d.midnight().assume_utc());
+        let entries = sqlx::query_as!(
+            GuestbookEntry,
+            r#"SELECT gb.id, gb.message, gb.signature, gb.created_at, gb.updated_at, gb.author_id,
+             gb.author_username, gb.author_provider as "author_provider: Provider", gb.reply,
+             gb.replied_at, gb.pinned_at, gb.hidden_at
+             FROM guestbook gb
+             WHERE ($1::timestamptz IS NULL
+                OR (COALESCE(gb.pinned_at, '-infinity'), gb.created_at, gb.id)
+                   < (COALESCE($10::timestamptz, '-infinity'), $1, $2))
+             AND ($6::text IS NULL
+                  OR to_tsvector('english', gb.message) @@ websearch_to_tsquery('english', $6))
+             AND ($7::text IS NULL OR lower(gb.author_username) = lower($7))
+             AND ($8::timestamptz IS NULL OR gb.created_at >= $8)
+             AND ($9::timestamptz IS NULL OR gb.created_at < $9)
+             AND ($5::bool OR gb.author_id = $4 OR (gb.hidden_at IS NULL AND NOT EXISTS (
+                 SELECT 1 FROM guests_groups gg
+                 JOIN groups g ON g.id = gg.group_id
+                 WHERE gg.guest_id = gb.author_id AND g.name = 'naughty_guests'
+             )))
+             ORDER BY COALESCE(gb.pinned_at, '-infinity') DESC, gb.created_at DESC, gb.id DESC
+             LIMIT $3"#,
+            after.map(|c| c.created_at), after.map(|c| c.id.as_value()), per_page as i64 + 1,
+            viewer.map(|v| v.as_value()), include_moderated, text, author, from, until,
+            after.and_then(|c| c.pinned_at)
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(Page::from_overfetch(entries, per_page, GuestbookEntry::cursor))
     }
 }
 #[cfg(test)]
@@ -182,4 +392,205 @@ mod tests {
         let result = repo.read(&GuestbookEntryCriteria::WithId(created_entry.id)).await;
         assert!(result.is_err());
     }
+    #[sqlx::test]
+    async fn test_naughty_entries_hidden(pool: PgPool) {
+        use crate::backend::{domain::models::Group, repos::GroupsAndPermissionsRepo};
+        let guest = setup_guest(&pool).await;
+        let repo = PgRepository::<GuestbookEntry>::new(pool.clone());
+        let entry = GuestbookEntry {
+            message: "Naughty".to_string(),
+            author_id: guest.id,
+            author_username: guest.username.clone(),
+            ..Default::default()
+        };
+        let entry = repo.create(&entry).await.unwrap();
+        assert!(repo.read_visible(entry.id, None).await.is_ok());
+        GroupsAndPermissionsRepo::new(pool)
+            .add_user_to_group(guest.id, Group::NaughtyGuests)
+            .await
+            .unwrap();
+        assert!(repo.read_visible_page(None, 10, None).await.unwrap().items.is_empty());
+        assert_eq!(repo.read_visible_page(None, 10, Some(guest.id)).await.unwrap().items.len(), 1);
+        assert_eq!(repo.read_page(None, 10).await.unwrap().items.len(), 1);
+        assert!(repo.read_visible(entry.id, None).await.is_err());
+        assert!(repo.read_visible(entry.id, Some(guest.id)).await.is_ok());
+    }
+    #[sqlx::test]
+    async fn test_cursor_pagination(pool: PgPool) {
+        use crate::backend::utils::setup_guests;
+        setup_guests(5, &pool).await;
+        // All entries share a timestamp, so the order hinges on the ID tie-breaker.
+        sqlx::query!(
+            "INSERT INTO guestbook (message, author_id, author_username, created_at)
+            SELECT 'Entry ' || id, id, username, '2024-01-01T00:00:00Z' FROM guests"
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = PgRepository::<GuestbookEntry>::new(pool.clone());
+        let first = repo.read_page(None, 2).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+        let cursor = first.next_cursor.expect("more pages should follow");
+        // A new entry must not shift the following pages.
+        let newcomer = setup_guest(&pool).await;
+        repo.create(&GuestbookEntry {
+            message: "Newcomer".to_string(),
+            author_id: newcomer.id,
+            author_username: newcomer.username.clone(),
+            ..Default::default()
+        })
+            .await
+            .unwrap();
+        let second = repo.read_page(Some(cursor), 2).await.unwrap();
+        let third = repo.read_page(second.next_cursor, 2).await.unwrap();
+        assert!(third.next_cursor.is_none());
+        let ids: Vec<i64> = [first.items, second.items, third.items]
+            .concat()
+            .iter()
+            .map(|e| e.id.as_value())
+            .collect();
+        assert_eq!(ids, vec![5, 4, 3, 2, 1]);
+    }
+    #[sqlx::test]
+    async fn test_pinned_entries_first(pool: PgPool) {
+        use crate::backend::utils::setup_guests;
+        setup_guests(5, &pool).await;
+        sqlx::query!(
+            "INSERT INTO guestbook (message, author_id, author_username, created_at)
+            SELECT 'Entry ' || id, id, username, '2024-01-01T00:00:00Z'::timestamptz
+                + make_interval(days => id::int)
+            FROM guests"
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = PgRepository::<GuestbookEntry>::new(pool.clone());
+        repo.set_pinned(GuestbookId(2), true).await.unwrap();
+        let pinned = repo.set_pinned(GuestbookId(4), true).await.unwrap();
+        // Pinning again keeps the original pin time.
+        let repinned = repo.set_pinned(GuestbookId(4), true).await.unwrap();
+        assert_eq!(repinned.pinned_at, pinned.pinned_at);
+        let mut ids = Vec::new();
+        let mut page = repo.read_page(None, 2).await.unwrap();
+        loop {
+            ids.extend(page.items.iter().map(|e| e.id.as_value()));
+            let Some(cursor) = page.next_cursor else { break };
+            page = repo.read_page(Some(cursor), 2).await.unwrap();
+        }
+        assert_eq!(ids, vec![4, 2, 5, 3, 1]);
+        repo.set_pinned(GuestbookId(1), true).await.unwrap();
+        assert!(matches!(
+            repo.set_pinned(GuestbookId(3), true).await,
+            Err(ApiError::ValidationError(_))
+        ));
+        assert!(matches!(
+            repo.set_pinned(GuestbookId(42), true).await,
+            Err(ApiError::NotFoundError(_))
+        ));
+        let unpinned = repo.set_pinned(GuestbookId(2), false).await.unwrap();
+        assert!(unpinned.pinned_at.is_none());
+        repo.set_pinned(GuestbookId(3), true).await.unwrap();
+        // A pinned entry hidden after being reported frees its slot.
+        sqlx::query!("UPDATE guestbook SET hidden_at = NOW() WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        repo.set_pinned(GuestbookId(5), true).await.unwrap();
+    }
+    #[sqlx::test]
+    async fn test_concurrent_pins_respect_limit(pool: PgPool) {
+        use crate::backend::utils::setup_guests;
+        setup_guests(6, &pool).await;
+        sqlx::query!(
+            "INSERT INTO guestbook (message, author_id, author_username)
+            SELECT 'Entry ' || id, id, username FROM guests"
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = PgRepository::<GuestbookEntry>::new(pool.clone());
+        let pins = (1..=6).map(|id| repo.set_pinned(GuestbookId(id), true));
+        let pinned = futures::future::join_all(pins)
+            .await
+            .into_iter()
+            .filter(Result::is_ok)
+            .count();
+        assert_eq!(pinned as i64, MAX_PINNED_ENTRIES);
+        let stored = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM guestbook WHERE pinned_at IS NOT NULL"#
+        )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, MAX_PINNED_ENTRIES);
+    }
+    #[sqlx::test]
+    async fn test_search(pool: PgPool) {
+        use crate::backend::utils::setup_guests;
+        use time::macros::date;
+        setup_guests(3, &pool).await;
+        sqlx::query!(
+            "INSERT INTO guestbook (message, author_id, author_username, created_at) VALUES
+            ('I love dogs', 1, 'testuser1', '2024-01-10T12:00:00Z'),
+            ('Cats are great', 2, 'testuser2', '2024-02-10T12:00:00Z'),
+            ('My dog says hi', 3, 'testuser3', '2024-03-10T12:00:00Z')"
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = PgRepository::<GuestbookEntry>::new(pool);
+        let messages = |page: Page<GuestbookEntry>| {
+            page.items.into_iter().map(|e| e.message).collect::<Vec<_>>()
+        };
+        let search = SignatureSearch {
+            text: "dog".to_string(),
+            ..Default::default()
+        };
+        let found = repo.search_page(&search, None, 10, None, false).await.unwrap();
+        assert_eq!(messages(found), vec!["My dog says hi", "I love dogs"]);
+        let search = SignatureSearch {
+            author: "TestUser2".to_string(),
+            ..Default::default()
+        };
+        let found = repo.search_page(&search, None, 10, None, false).await.unwrap();
+        assert_eq!(messages(found), vec!["Cats are great"]);
+        let search = SignatureSearch {
+            from: Some(date!(2024 - 01 - 11)),
+            to: Some(date!(2024 - 03 - 10)),
+            ..Default::default()
+        };
+        let found = repo.search_page(&search, None, 10, None, false).await.unwrap();
+        assert_eq!(messages(found), vec!["My dog says hi", "Cats are great"]);
+        let search = SignatureSearch {
+            text: "dog".to_string(),
+            ..Default::default()
+        };
+        let first = repo.search_page(&search, None, 1, None, false).await.unwrap();
+        let second = repo.search_page(&search, first.next_cursor, 1, None, false).await.unwrap();
+        assert_eq!(messages(second.clone()), vec!["I love dogs"]);
+        assert!(second.next_cursor.is_none());
+    }
+    #[sqlx::test]
+    async fn test_set_reply(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let repo = PgRepository::<GuestbookEntry>::new(pool);
+        let entry = repo
+            .create(&GuestbookEntry {
+                message: "Hi there".to_string(),
+                author_id: guest.id,
+                author_username: guest.username.clone(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(entry.reply.is_none());
+        let replied = repo.set_reply(entry.id, Some("Thanks!")).await.unwrap();
+        assert_eq!(replied.reply.as_deref(), Some("Thanks!"));
+        assert!(replied.replied_at.is_some());
+        assert_eq!(replied.updated_at, entry.updated_at);
+        let cleared = repo.set_reply(entry.id, None).await.unwrap();
+        assert!(cleared.reply.is_none());
+        assert!(cleared.replied_at.is_none());
+        assert!(repo.set_reply(GuestbookId(entry.id.as_value() + 1), Some("?")).await.is_err());
+    }
 }
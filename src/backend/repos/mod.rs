@@ -16,3 +16,12 @@ pub use guest_repo::*;
 /// Repo to handle user and group authorization scopes
 mod authorization_repo;
 pub use authorization_repo::*;
+/// Repo to track and revoke the sessions of logged in guests
+mod session_repo;
+pub use session_repo::*;
+/// Repo for reactions on guestbook entries
+mod reaction_repo;
+pub use reaction_repo::*;
+/// Repo for reports filed against guestbook entries
+mod report_repo;
+pub use report_repo::*;
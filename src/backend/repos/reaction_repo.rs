@@ -0,0 +1,182 @@
+//! This module provides access to the reactions guests leave on guestbook entries.
+use crate::backend::errors::BResult;
+use crate::shared::models::{
+    EntryWithReactions, GivenReaction, GuestId, GuestbookEntry, GuestbookId, Page, Reaction,
+    ReactionCount,
+};
+use std::collections::HashMap;
+#[derive(Clone, Debug)]
+pub struct ReactionRepo {
+    pool: sqlx::PgPool,
+}
+impl ReactionRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+    /// Adds a guest's reaction to an entry. Adding it again has no effect.
+    pub async fn add(
+        &self,
+        entry_id: GuestbookId,
+        guest_id: GuestId,
+        reaction: Reaction,
+    ) -> BResult<()> {
+        sqlx::query!(
+            "INSERT INTO guestbook_reactions (entry_id, guest_id, reaction)
+             VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING",
+            entry_id.as_value(), guest_id.as_value(), reaction as Reaction
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Removes a guest's reaction from an entry. Removing a missing reaction has no effect.
+    pub async fn remove(
+        &self,
+        entry_id: GuestbookId,
+        guest_id: GuestId,
+        reaction: Reaction,
+    ) -> BResult<()> {
+        sqlx::query!(
+            "DELETE FROM guestbook_reactions
+             WHERE entry_id = $1 AND guest_id = $2 AND reaction = $3",
+            entry_id.as_value(), guest_id.as_value(), reaction as Reaction
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Counts the reactions on each of the given entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_ids` - The entries to count reactions for
+    /// * `viewer` - The guest whose own reactions are flagged, if any
+    pub async fn counts(
+        &self,
+        entry_ids: &[GuestbookId],
+        viewer: Option<GuestId>,
+    ) -> BResult<HashMap<GuestbookId, Vec<ReactionCount>>> {
+        let ids: Vec<i64> = entry_ids.iter().map(GuestbookId::as_value).collect();
+        let rows = sqlx::query!(
+            r#"SELECT entry_id, reaction as "reaction: Reaction", COUNT(*) as "count!",
+             COALESCE(BOOL_OR(guest_id = $2), false) as "reacted!"
+             FROM guestbook_reactions
+             WHERE entry_id = ANY($1)
+             GROUP BY entry_id, reaction
+             ORDER BY entry_id, reaction"#,
+            &ids, viewer.map(|v| v.as_value())
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        let mut counts: HashMap<GuestbookId, Vec<ReactionCount>> = HashMap::new();
+        for row in rows {
+            counts
+                .entry(GuestbookId(row.entry_id))
+                .or_default()
+                .push(ReactionCount {
+                    reaction: row.reaction,
+                    count: row.count,
+                    reacted: row.reacted,
+                });
+        }
+        Ok(counts)
+    }
+    /// Retrieves all reactions a guest left, newest first.
+    pub async fn by_guest(&self, guest_id: GuestId) -> BResult<Vec<GivenReaction>> {
+        let rows = sqlx::query!(
+            r#"SELECT entry_id, reaction as "reaction: Reaction", created_at
+             FROM guestbook_reactions
+             WHERE guest_id = $1
+             ORDER BY created_at DESC, entry_id, reaction"#,
+            guest_id.as_value()
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(
+            rows
+                .into_iter()
+                .map(|row| GivenReaction {
+                    entry_id: GuestbookId(row.entry_id),
+                    reaction: row.reaction,
+                    created_at: row.created_at,
+                })
+                .collect(),
+        )
+    }
+    /// Attaches the reactions to each entry on a page.
+    pub async fn attach(
+        &self,
+        page: Page<GuestbookEntry>,
+        viewer: Option<GuestId>,
+    ) -> BResult<Page<EntryWithReactions>> {
+        let ids: Vec<GuestbookId> = page.items.iter().map(|entry| entry.id).collect();
+        let mut counts = self.counts(&ids, viewer).await?;
+        Ok(
+            page.map(|entry| EntryWithReactions {
+                reactions: counts.remove(&entry.id).unwrap_or_default(),
+                entry,
+            }),
+        )
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::repos::{PgRepository, Repository};
+    use crate::backend::utils::{setup_guest, setup_guests};
+    use sqlx::PgPool;
+    #[sqlx::test]
+    async fn test_reactions_are_idempotent(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let entry = PgRepository::<GuestbookEntry>::new(pool.clone())
+            .create(&GuestbookEntry {
+                message: "React to me".to_string(),
+                author_id: guest.id,
+                author_username: guest.username.clone(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = ReactionRepo::new(pool);
+        repo.add(entry.id, guest.id, Reaction::Heart).await.unwrap();
+        repo.add(entry.id, guest.id, Reaction::Heart).await.unwrap();
+        let counts = repo.counts(&[entry.id], Some(guest.id)).await.unwrap();
+        assert_eq!(
+            counts[&entry.id],
+            vec![ReactionCount { reaction: Reaction::Heart, count: 1, reacted: true }]
+        );
+        repo.remove(entry.id, guest.id, Reaction::Heart).await.unwrap();
+        repo.remove(entry.id, guest.id, Reaction::Heart).await.unwrap();
+        assert!(repo.counts(&[entry.id], Some(guest.id)).await.unwrap().is_empty());
+    }
+    #[sqlx::test]
+    async fn test_reaction_counts(pool: PgPool) {
+        setup_guests(3, &pool).await;
+        let entry = PgRepository::<GuestbookEntry>::new(pool.clone())
+            .create(&GuestbookEntry {
+                message: "Popular".to_string(),
+                author_id: GuestId(1),
+                author_username: "testuser1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = ReactionRepo::new(pool);
+        for guest in 1..=3 {
+            repo.add(entry.id, GuestId(guest), Reaction::Fire).await.unwrap();
+        }
+        repo.add(entry.id, GuestId(2), Reaction::Wave).await.unwrap();
+        let page = Page { items: vec![entry.clone()], next_cursor: None };
+        let page = repo.attach(page, Some(GuestId(1))).await.unwrap();
+        assert_eq!(
+            page.items[0].reactions,
+            vec![
+                ReactionCount { reaction: Reaction::Wave, count: 1, reacted: false },
+                ReactionCount { reaction: Reaction::Fire, count: 3, reacted: true },
+            ]
+        );
+        let anonymous = repo.counts(&[entry.id], None).await.unwrap();
+        assert!(anonymous[&entry.id].iter().all(|c| !c.reacted));
+    }
+}
@@ -0,0 +1,212 @@
+//! This module provides access to the reports guests file against guestbook entries.
+//!
+//! Once an entry received `threshold` reports it is hidden from the public listing
+//! until a moderator reviews it, either dismissing the reports or deleting the entry.
+use crate::backend::errors::BResult;
+use crate::shared::models::{
+    FiledReport, GuestId, GuestbookEntry, GuestbookId, Provider, Report, ReportReason,
+    ReportedEntry,
+};
+use std::collections::HashMap;
+#[derive(Clone, Debug)]
+pub struct ReportRepo {
+    pool: sqlx::PgPool,
+    threshold: i64,
+}
+impl ReportRepo {
+    /// Creates a repo hiding entries once they received `threshold` reports.
+    pub fn new(pool: sqlx::PgPool, threshold: i64) -> Self {
+        Self { pool, threshold }
+    }
+    /// Files a guest's report against an entry, hiding the entry if it now received
+    /// enough reports.
+    ///
+    /// Each guest can report an entry once; reporting it again updates the reason.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entry is hidden.
+    pub async fn report(
+        &self,
+        entry_id: GuestbookId,
+        reporter_id: GuestId,
+        reason: ReportReason,
+    ) -> BResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        // Concurrent reports could otherwise each miss the others' uncommitted rows and all
+        // count below the threshold.
+        sqlx::query!("SELECT id FROM guestbook WHERE id = $1 FOR UPDATE", entry_id.as_value())
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query!(
+            "INSERT INTO reports (entry_id, reporter_id, reason)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (entry_id, reporter_id) DO UPDATE SET reason = excluded.reason",
+            entry_id.as_value(), reporter_id.as_value(), reason as ReportReason
+        )
+            .execute(&mut *tx)
+            .await?;
+        let hidden = sqlx::query_scalar!(
+            r#"UPDATE guestbook
+             SET hidden_at = COALESCE(hidden_at, NOW())
+             WHERE id = $1 AND (SELECT COUNT(*) FROM reports WHERE entry_id = $1) >= $2
+             RETURNING id"#,
+            entry_id.as_value(), self.threshold
+        )
+            .fetch_optional(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(hidden.is_some())
+    }
+    /// Retrieves all reported entries along with their reports.
+    ///
+    /// Hidden entries come first, then the entries reported most recently.
+    pub async fn queue(&self) -> BResult<Vec<ReportedEntry>> {
+        let entries = sqlx::query_as!(
+            GuestbookEntry,
+            r#"SELECT gb.id, gb.message, gb.signature, gb.created_at, gb.updated_at, gb.author_id,
+             gb.author_username, gb.author_provider as "author_provider: Provider", gb.reply,
+             gb.replied_at, gb.pinned_at, gb.hidden_at
+             FROM guestbook gb
+             JOIN (
+                 SELECT entry_id, MAX(created_at) AS last_reported_at
+                 FROM reports GROUP BY entry_id
+             ) r ON r.entry_id = gb.id
+             ORDER BY gb.hidden_at IS NULL, r.last_reported_at DESC"#
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        let ids: Vec<i64> = entries.iter().map(|entry| entry.id.as_value()).collect();
+        let rows = sqlx::query!(
+            r#"SELECT r.entry_id, g.username, r.reason as "reason: ReportReason", r.created_at
+             FROM reports r
+             JOIN guests g ON g.id = r.reporter_id
+             WHERE r.entry_id = ANY($1)
+             ORDER BY r.created_at DESC"#,
+            &ids
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        let mut reports: HashMap<GuestbookId, Vec<Report>> = HashMap::new();
+        for row in rows {
+            reports
+                .entry(GuestbookId(row.entry_id))
+                .or_default()
+                .push(Report {
+                    reporter_username: row.username,
+                    reason: row.reason,
+                    created_at: row.created_at,
+                });
+        }
+        Ok(
+            entries
+                .into_iter()
+                .map(|entry| ReportedEntry {
+                    reports: reports.remove(&entry.id).unwrap_or_default(),
+                    entry,
+                })
+                .collect(),
+        )
+    }
+    /// Retrieves all reports a guest filed, newest first.
+    pub async fn by_reporter(&self, reporter_id: GuestId) -> BResult<Vec<FiledReport>> {
+        let rows = sqlx::query!(
+            r#"SELECT entry_id, reason as "reason: ReportReason", created_at
+             FROM reports
+             WHERE reporter_id = $1
+             ORDER BY created_at DESC, entry_id"#,
+            reporter_id.as_value()
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(
+            rows
+                .into_iter()
+                .map(|row| FiledReport {
+                    entry_id: GuestbookId(row.entry_id),
+                    reason: row.reason,
+                    created_at: row.created_at,
+                })
+                .collect(),
+        )
+    }
+    /// Discards all reports against an entry and shows it again if it was hidden.
+    pub async fn dismiss(&self, entry_id: GuestbookId) -> BResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!("DELETE FROM reports WHERE entry_id = $1", entry_id.as_value())
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!(
+            "UPDATE guestbook SET hidden_at = NULL WHERE id = $1", entry_id.as_value()
+        )
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::repos::{PgRepository, Repository};
+    use crate::backend::utils::setup_guests;
+    use sqlx::PgPool;
+    #[sqlx::test]
+    async fn test_reports_hide_entry(pool: PgPool) {
+        setup_guests(3, &pool).await;
+        let guestbook = PgRepository::<GuestbookEntry>::new(pool.clone());
+        let entry = guestbook
+            .create(&GuestbookEntry {
+                message: "Buy cheap watches".to_string(),
+                author_id: GuestId(1),
+                author_username: "testuser1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = ReportRepo::new(pool.clone(), 2);
+        assert!(!repo.report(entry.id, GuestId(2), ReportReason::Other).await.unwrap());
+        // Reporting twice counts once.
+        assert!(!repo.report(entry.id, GuestId(2), ReportReason::Spam).await.unwrap());
+        let visible = guestbook.read_visible_page(None, 10, Some(GuestId(2))).await.unwrap();
+        assert_eq!(visible.items.len(), 1);
+        assert!(repo.report(entry.id, GuestId(3), ReportReason::Spam).await.unwrap());
+        let visible = guestbook.read_visible_page(None, 10, Some(GuestId(2))).await.unwrap();
+        assert!(visible.items.is_empty());
+        // The author still sees their own entry.
+        let own = guestbook.read_visible_page(None, 10, Some(GuestId(1))).await.unwrap();
+        assert_eq!(own.items.len(), 1);
+        let queue = repo.queue().await.unwrap();
+        assert_eq!(queue.len(), 1);
+        assert!(queue[0].entry.hidden_at.is_some());
+        assert_eq!(queue[0].reports.len(), 2);
+        assert!(queue[0].reports.iter().all(|r| r.reason == ReportReason::Spam));
+        repo.dismiss(entry.id).await.unwrap();
+        assert!(repo.queue().await.unwrap().is_empty());
+        let visible = guestbook.read_visible_page(None, 10, None).await.unwrap();
+        assert_eq!(visible.items.len(), 1);
+    }
+    #[sqlx::test]
+    async fn test_concurrent_reports_hide_entry(pool: PgPool) {
+        setup_guests(6, &pool).await;
+        let entry = PgRepository::<GuestbookEntry>::new(pool.clone())
+            .create(&GuestbookEntry {
+                message: "Buy cheap watches".to_string(),
+                author_id: GuestId(1),
+                author_username: "testuser1".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let repo = ReportRepo::new(pool.clone(), 5);
+        let reports = (2..=6).map(|id| repo.report(entry.id, GuestId(id), ReportReason::Spam));
+        let hidden: Vec<bool> = futures::future::join_all(reports)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(hidden.iter().filter(|hidden| **hidden).count(), 1);
+        let queue = repo.queue().await.unwrap();
+        assert!(queue[0].entry.hidden_at.is_some());
+    }
+}
@@ -0,0 +1,189 @@
+//! This module provides access to the sessions of logged in guests.
+//!
+//! Sessions themselves live in the `tower_sessions.session` table owned by the session
+//! store. The `guest_sessions` table keeps metadata about sessions that belong to a
+//! guest, and is cleaned up by cascading deletes whenever a session goes away.
+use crate::backend::errors::BResult;
+use crate::shared::models::{ActiveSession, GuestId};
+use time::OffsetDateTime;
+/// A row of the `guest_sessions` table.
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    pub id: i64,
+    pub session_id: String,
+    pub created_at: OffsetDateTime,
+    pub last_seen_at: OffsetDateTime,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+impl SessionRecord {
+    /// Converts the record to its public form, flagging it if it is `current_session`.
+    pub fn into_active(self, current_session: Option<&str>) -> ActiveSession {
+        ActiveSession {
+            current: current_session == Some(self.session_id.as_str()),
+            id: self.id,
+            created_at: self.created_at,
+            last_seen_at: self.last_seen_at,
+            user_agent: self.user_agent,
+            ip: self.ip,
+        }
+    }
+}
+#[derive(Clone, Debug)]
+pub struct SessionRepo {
+    pool: sqlx::PgPool,
+}
+impl SessionRepo {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+    /// Associates a stored session with the guest who logged in with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The ID of the session in the session store.
+    /// * `guest_id` - The ID of the guest.
+    /// * `user_agent` - The user agent of the login request, if any.
+    /// * `ip` - The coarse IP address of the login request, if known.
+    pub async fn record(
+        &self,
+        session_id: &str,
+        guest_id: GuestId,
+        user_agent: Option<&str>,
+        ip: Option<&str>,
+    ) -> BResult<()> {
+        sqlx::query!(
+            "INSERT INTO guest_sessions (session_id, guest_id, user_agent, ip)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (session_id) DO UPDATE
+            SET guest_id = excluded.guest_id, user_agent = excluded.user_agent,
+                ip = excluded.ip, last_seen_at = NOW()",
+            session_id, guest_id.as_value(), user_agent, ip
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Marks a session as used now.
+    ///
+    /// Updates are throttled to once a minute per session to keep request overhead low.
+    pub async fn touch(&self, session_id: &str) -> BResult<()> {
+        sqlx::query!(
+            "UPDATE guest_sessions SET last_seen_at = NOW()
+            WHERE session_id = $1 AND last_seen_at < NOW() - INTERVAL '1 minute'",
+            session_id
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Retrieves the unexpired sessions of a guest, most recently used first.
+    pub async fn list_active(&self, guest_id: GuestId) -> BResult<Vec<SessionRecord>> {
+        let sessions = sqlx::query_as!(
+            SessionRecord,
+            "SELECT gs.id, gs.session_id, gs.created_at, gs.last_seen_at, gs.user_agent, gs.ip
+            FROM guest_sessions gs
+            JOIN tower_sessions.session s ON s.id = gs.session_id
+            WHERE gs.guest_id = $1 AND s.expiry_date > NOW()
+            ORDER BY gs.last_seen_at DESC",
+            guest_id.as_value()
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(sessions)
+    }
+    /// Deletes one of a guest's sessions from the session store.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the session existed and belonged to the guest.
+    pub async fn revoke(&self, guest_id: GuestId, id: i64) -> BResult<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM tower_sessions.session s
+            USING guest_sessions gs
+            WHERE gs.session_id = s.id AND gs.guest_id = $1 AND gs.id = $2",
+            guest_id.as_value(), id
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+    /// Deletes all of a guest's sessions from the session store.
+    ///
+    /// # Returns
+    ///
+    /// The number of deleted sessions.
+    pub async fn revoke_all(&self, guest_id: GuestId) -> BResult<u64> {
+        Self::revoke_all_with(&self.pool, guest_id).await
+    }
+    /// Deletes all of a guest's sessions using the given executor.
+    ///
+    /// This allows the delete to take part in a larger transaction.
+    pub async fn revoke_all_with<'e, E>(executor: E, guest_id: GuestId) -> BResult<u64>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM tower_sessions.session s
+            USING guest_sessions gs
+            WHERE gs.session_id = s.id AND gs.guest_id = $1",
+            guest_id.as_value()
+        )
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::utils::setup_guest;
+    use sqlx::PgPool;
+    async fn store_session(pool: &PgPool, id: &str, expires_in_hours: i32) {
+        sqlx::query!(
+            "INSERT INTO tower_sessions.session (id, data, expiry_date)
+            VALUES ($1, '', NOW() + make_interval(hours => $2))",
+            id, expires_in_hours
+        )
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+    #[sqlx::test]
+    async fn test_list_active_sessions(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let repo = SessionRepo::new(pool.clone());
+        store_session(&pool, "current", 24).await;
+        store_session(&pool, "expired", -1).await;
+        repo.record("current", guest.id, Some("Firefox"), Some("203.0.113.0/24")).await.unwrap();
+        repo.record("expired", guest.id, None, None).await.unwrap();
+        let sessions = repo.list_active(guest.id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        let session = sessions[0].clone().into_active(Some("current"));
+        assert!(session.current);
+        assert_eq!(session.user_agent.as_deref(), Some("Firefox"));
+        assert_eq!(session.ip.as_deref(), Some("203.0.113.0/24"));
+    }
+    #[sqlx::test]
+    async fn test_revoke_sessions(pool: PgPool) {
+        let guest = setup_guest(&pool).await;
+        let repo = SessionRepo::new(pool.clone());
+        for id in ["a", "b", "c"] {
+            store_session(&pool, id, 24).await;
+            repo.record(id, guest.id, None, None).await.unwrap();
+        }
+        let sessions = repo.list_active(guest.id).await.unwrap();
+        let target = sessions.iter().find(|s| s.session_id == "a").unwrap();
+        let other_guest = GuestId(guest.id.as_value() + 1);
+        assert!(!repo.revoke(other_guest, target.id).await.unwrap());
+        assert!(repo.revoke(guest.id, target.id).await.unwrap());
+        assert_eq!(repo.list_active(guest.id).await.unwrap().len(), 2);
+        assert_eq!(repo.revoke_all(guest.id).await.unwrap(), 2);
+        assert!(repo.list_active(guest.id).await.unwrap().is_empty());
+        let stored = sqlx::query_scalar!("SELECT COUNT(*) FROM tower_sessions.session")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, Some(0));
+    }
+}
@@ -1,8 +1,11 @@
 use crate::backend::AppState;
 use crate::backend::config::AppConfig;
 use crate::backend::domain::logic::AuthBackend;
-use crate::backend::domain::logic::oauth::build_oauth_client;
+use crate::backend::domain::logic::providers::Providers;
+use crate::backend::domain::logic::sessions::track_activity;
 use crate::backend::extractors::CookieExtractor;
+use crate::backend::session_keys::{SessionKeys, rotate_session_cookie};
+use crate::backend::token_cipher::TokenCipher;
 use crate::backend::wapi::api_router;
 use axum::Router;
 use axum_login::AuthManagerLayerBuilder;
@@ -21,18 +24,25 @@ pub async fn serve(cfg: impl Into<ServeConfig>, dxapp: fn() -> Element) {
     let postgres = sqlx::PgPool::connect(config.database.url.as_str()).await.unwrap();
     dioxus_logger::tracing::info!("Running database migration..");
     sqlx::migrate!().run(&postgres).await.expect("Failed to run migrations");
-    let (domain, client_id, client_secret) = (
-        config.domain.as_str(),
-        config.gabioinf.id.as_str(),
-        config.gabioinf.secret.as_str(),
+    let domain = config.domain.as_str();
+    let providers = Providers::from_config(&config);
+    dioxus_logger::tracing::info!("Configured OAuth providers: {:?}", providers.kinds());
+    // OAuth endpoints must not redirect; following them could leak codes and tokens.
+    let reqwest_client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to build HTTP client");
+    let session_keys = Arc::new(
+        SessionKeys::from_config(&config.session, config.run_mode)
+            .expect("Failed to load session keys"),
     );
-    let client = build_oauth_client(client_id, client_secret, domain);
-    let reqwest_client = reqwest::Client::new();
     let state = AppState::new(
         postgres.clone(),
         domain.to_string(),
-        client.clone(),
         reqwest_client.clone(),
+        session_keys.current.clone(),
+        config.redirects.allowed.clone(),
+        config.moderation.threshold,
     );
     let session_store = PostgresStore::new(postgres.clone());
     session_store.migrate().await.unwrap();
@@ -49,8 +59,11 @@ pub async fn serve(cfg: impl Into<ServeConfig>, dxapp: fn() -> Element) {
     let backend = AuthBackend::new(
         state.guest_repo.clone(),
         state.gp_repo.clone(),
-        client,
+        providers,
         reqwest_client,
+        config.admins.clone(),
+        TokenCipher::from_config(&config.encryption, config.run_mode)
+            .expect("Failed to load encryption key"),
     );
     let auth_layer = AuthManagerLayerBuilder::new(backend, session_layer).build();
     let governor_conf = Arc::new(
@@ -71,11 +84,13 @@ pub async fn serve(cfg: impl Into<ServeConfig>, dxapp: fn() -> Element) {
             governor_limiter.retain_recent();
         }
     });
+    let session_repo = state.session_repo.clone();
     let cfg = cfg.into();
     let ssr_state = SSRState::new(&cfg);
+    // Static assets are added after the activity tracking layer, so loading them does not
+    // cost a database round trip each.
     let app = Router::new()
         .nest("/v1/", api_router(state.clone(), governor_conf))
-        .serve_static_assets()
         .register_server_functions_with_context(
             Arc::new(vec![Box::new(move || { Box::new(state.clone()) })]),
         )
@@ -83,7 +98,10 @@ pub async fn serve(cfg: impl Into<ServeConfig>, dxapp: fn() -> Element) {
             axum::routing::get(render_handler)
                 .with_state(RenderHandleState::new(cfg, dxapp).with_ssr_state(ssr_state)),
         )
-        .layer(auth_layer);
+        .layer(axum::middleware::from_fn_with_state(session_repo, track_activity))
+        .serve_static_assets()
+        .layer(auth_layer)
+        .layer(axum::middleware::from_fn_with_state(session_keys, rotate_session_cookie));
     use std::net::SocketAddr;
     let port = dioxus_cli_config::server_port().unwrap_or(8080);
     let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), port);
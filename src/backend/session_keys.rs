@@ -0,0 +1,177 @@
+//! Cookie signing keys and key rotation.
+//!
+//! Session cookies are signed with a key loaded from [`SessionConfig`]. To rotate
+//! keys without logging everyone out, previous keys can be listed in the configuration:
+//! cookies signed with one of them are transparently re-signed with the current key
+//! before they reach the session layer.
+use crate::backend::{
+    config::{RunMode, SessionConfig},
+    utils::decode_secret,
+};
+use anyhow::{Context, anyhow};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, header::COOKIE};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+use tower_sessions::cookie::{Cookie, CookieJar, Key};
+/// Name of the cookie holding the session ID, as set by `tower_sessions`.
+pub const SESSION_COOKIE: &str = "id";
+/// The current signing key along with keys that are still accepted for verification.
+#[derive(Clone)]
+pub struct SessionKeys {
+    /// Key used to sign new cookies.
+    pub current: Key,
+    /// Keys whose signatures are still accepted during the rotation grace window.
+    pub previous: Vec<Key>,
+}
+impl std::fmt::Debug for SessionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionKeys")
+            .field("current", &"********")
+            .field("previous", &self.previous.len())
+            .finish()
+    }
+}
+impl SessionKeys {
+    /// Loads the keys from the configuration.
+    ///
+    /// If no key is configured, a random one is generated in development, while
+    /// production refuses to start.
+    pub fn from_config(config: &SessionConfig, run_mode: RunMode) -> anyhow::Result<Self> {
+        let current = match &config.key {
+            Some(secret) => decode_key(secret).context("Invalid session key")?,
+            None if run_mode == RunMode::Development => {
+                dioxus_logger::tracing::warn!(
+                    "No session key configured, sessions will not survive a restart"
+                );
+                Key::generate()
+            }
+            None => return Err(anyhow!("A session key must be configured in production")),
+        };
+        let previous = config
+            .previous
+            .iter()
+            .map(|secret| decode_key(secret))
+            .collect::<anyhow::Result<_>>()
+            .context("Invalid previous session key")?;
+        Ok(Self { current, previous })
+    }
+    /// Re-signs the session cookie in `headers` with the current key if it was
+    /// signed with one of the previous keys.
+    ///
+    /// Cookies that are already valid under the current key, or not valid under any
+    /// key, are left untouched.
+    pub fn resign(&self, headers: &mut HeaderMap) {
+        let mut jar = CookieJar::new();
+        headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(Cookie::split_parse)
+            .filter_map(Result::ok)
+            .for_each(|cookie| jar.add_original(cookie.into_owned()));
+        if jar.get(SESSION_COOKIE).is_none()
+            || jar.signed(&self.current).get(SESSION_COOKIE).is_some()
+        {
+            return;
+        }
+        let verified = self.previous.iter().find_map(|key| jar.signed(key).get(SESSION_COOKIE));
+        let Some(verified) = verified else {
+            return;
+        };
+        jar.signed_mut(&self.current).add(verified);
+        let header = jar
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name(), cookie.value()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if let Ok(value) = HeaderValue::from_str(&header) {
+            headers.remove(COOKIE);
+            headers.insert(COOKIE, value);
+        }
+    }
+}
+/// Decodes a signing key from a hex or base64 encoded secret.
+///
+/// The decoded secret must be at least 64 bytes long.
+pub fn decode_key(secret: &str) -> anyhow::Result<Key> {
+    let bytes = decode_secret(secret)?;
+    Key::try_from(bytes.as_slice()).map_err(|_| anyhow!("Key must be at least 64 bytes long"))
+}
+/// Middleware re-signing session cookies from previous keys, see [`SessionKeys::resign`].
+///
+/// Must wrap the session layer so that it runs first.
+pub async fn rotate_session_cookie(
+    State(keys): State<Arc<SessionKeys>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if !keys.previous.is_empty() {
+        keys.resign(req.headers_mut());
+    }
+    next.run(req).await
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    fn signed_header(key: &Key, value: &str) -> HeaderMap {
+        let mut jar = CookieJar::new();
+        jar.signed_mut(key).add(Cookie::new(SESSION_COOKIE, value.to_string()));
+        let cookie = jar.get(SESSION_COOKIE).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("other=1; {}={}", cookie.name(), cookie.value()))
+                .unwrap(),
+        );
+        headers
+    }
+    fn verify(headers: &HeaderMap, key: &Key) -> Option<String> {
+        let mut jar = CookieJar::new();
+        let header = headers.get(COOKIE)?.to_str().ok()?.to_string();
+        Cookie::split_parse(header)
+            .filter_map(Result::ok)
+            .for_each(|cookie| jar.add_original(cookie.into_owned()));
+        jar.signed(key).get(SESSION_COOKIE).map(|c| c.value().to_string())
+    }
+    #[test]
+    fn test_decode_key() {
+        let raw = [7u8; 64];
+        assert!(decode_key(&hex::encode(raw)).is_ok());
+        assert!(decode_key(&base64::engine::general_purpose::STANDARD.encode(raw)).is_ok());
+        assert!(decode_key(&hex::encode([7u8; 32])).is_err());
+        assert!(decode_key("not a key!").is_err());
+    }
+    #[test]
+    fn test_missing_key() {
+        let config = SessionConfig::default();
+        assert!(SessionKeys::from_config(&config, RunMode::Development).is_ok());
+        assert!(SessionKeys::from_config(&config, RunMode::Production).is_err());
+    }
+    #[test]
+    fn test_resign_previous_key() {
+        let (old, new) = (Key::generate(), Key::generate());
+        let keys = SessionKeys {
+            current: new.clone(),
+            previous: vec![old.clone()],
+        };
+        let mut headers = signed_header(&old, "session");
+        keys.resign(&mut headers);
+        assert_eq!(verify(&headers, &new).as_deref(), Some("session"));
+        assert!(headers.get(COOKIE).unwrap().to_str().unwrap().contains("other=1"));
+    }
+    #[test]
+    fn test_resign_ignores_unknown_key() {
+        let keys = SessionKeys {
+            current: Key::generate(),
+            previous: vec![Key::generate()],
+        };
+        let stranger = Key::generate();
+        let mut headers = signed_header(&stranger, "session");
+        keys.resign(&mut headers);
+        assert_eq!(verify(&headers, &stranger).as_deref(), Some("session"));
+        assert_eq!(verify(&headers, &keys.current), None);
+    }
+}
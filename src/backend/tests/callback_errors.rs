@@ -0,0 +1,98 @@
+//! How failed OAuth callbacks are reported.
+use super::{TestApp, json_body, location, mock_github};
+use axum::http::StatusCode;
+use sqlx::PgPool;
+#[sqlx::test]
+async fn test_denied_access_redirects_to_error_page(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login?next=/guestbook").await;
+    let response = app
+        .get(
+            &format!("/v1/oauth/callback?error=access_denied&state={state}"),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(location(&response), "/auth/error?reason=denied");
+}
+#[sqlx::test]
+async fn test_provider_error_as_json(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login").await;
+    let response = app
+        .get_json(
+            &format!(
+                "/v1/oauth/callback?error=temporarily_unavailable&error_description=later&state={state}"
+            ),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "provider");
+}
+#[sqlx::test]
+async fn test_csrf_mismatch_redirects_to_error_page(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (_, cookie) = app.start_login("/v1/login").await;
+    let response = app
+        .get(
+            &format!("/v1/oauth/callback?code={}&state=forged", mock_github::CODE),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(location(&response), "/auth/error?reason=state");
+}
+#[sqlx::test]
+async fn test_forged_error_keeps_pending_login(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login").await;
+    let response = app
+        .get_json("/v1/oauth/callback?error=access_denied&state=forged", Some(&cookie))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "state");
+    let response = app
+        .get(
+            &format!("/v1/oauth/callback?code={}&state={state}", mock_github::CODE),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(location(&response), "/");
+}
+#[sqlx::test]
+async fn test_state_cannot_be_replayed(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login").await;
+    let callback = format!("/v1/oauth/callback?code=wrong&state={state}");
+    let response = app.get_json(&callback, Some(&cookie)).await;
+    assert_eq!(json_body(response).await["error"], "token");
+    let response = app.get_json(&callback, Some(&cookie)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(response).await["error"], "state");
+}
+#[sqlx::test]
+async fn test_token_exchange_failure_as_json(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login").await;
+    let response = app
+        .get_json(&format!("/v1/oauth/callback?code=wrong&state={state}"), Some(&cookie))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    let body = json_body(response).await;
+    assert_eq!(body["error"], "token");
+    assert!(body["message"].as_str().is_some_and(|m| !m.is_empty()));
+}
+#[sqlx::test]
+async fn test_user_fetch_failure(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login").await;
+    let callback = format!(
+        "/v1/oauth/callback?code={}&state={state}",
+        mock_github::BROKEN_PROFILE_CODE
+    );
+    let response = app.get(&callback, Some(&cookie)).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(location(&response), "/auth/error?reason=profile");
+}
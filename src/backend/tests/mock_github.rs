@@ -0,0 +1,65 @@
+//! A minimal stand-in for GitHub's OAuth and user endpoints.
+use crate::backend::domain::logic::providers::GitHub;
+use axum::{
+    Form, Json, Router,
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde_json::json;
+use std::collections::HashMap;
+/// The authorization code the mock accepts.
+pub(crate) const CODE: &str = "mock-code";
+/// The access token the mock issues for [`CODE`].
+pub(crate) const ACCESS_TOKEN: &str = "mock-access-token";
+/// An authorization code the mock accepts, but whose token the user endpoint rejects.
+pub(crate) const BROKEN_PROFILE_CODE: &str = "mock-broken-profile-code";
+/// The GitHub user the mock authenticates.
+pub(crate) const USER_ID: i64 = 4242;
+pub(crate) const USER_LOGIN: &str = "octocat";
+/// A mock GitHub listening on a random local port.
+pub(crate) struct MockGithub {
+    pub url: String,
+}
+impl MockGithub {
+    pub async fn spawn() -> Self {
+        let app = Router::new()
+            .route("/login/oauth/access_token", post(access_token))
+            .route("/user", get(user));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        Self { url }
+    }
+    /// A GitHub provider whose endpoints all point at this mock.
+    pub fn provider(&self) -> GitHub {
+        GitHub {
+            authorize: format!("{}/login/oauth/authorize", self.url),
+            token: format!("{}/login/oauth/access_token", self.url),
+            api: self.url.clone(),
+        }
+    }
+}
+async fn access_token(Form(params): Form<HashMap<String, String>>) -> Response {
+    let token = match params.get("code").map(String::as_str) {
+        Some(CODE) => ACCESS_TOKEN,
+        Some(BROKEN_PROFILE_CODE) => "mock-revoked-token",
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "bad_verification_code" })),
+            )
+                .into_response();
+        }
+    };
+    Json(json!({ "access_token": token, "token_type": "bearer", "scope": "" }))
+        .into_response()
+}
+async fn user(headers: HeaderMap) -> Response {
+    let expected = format!("Bearer {ACCESS_TOKEN}");
+    if headers.get(AUTHORIZATION).and_then(|v| v.to_str().ok()) != Some(expected.as_str()) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    Json(json!({ "id": USER_ID, "login": USER_LOGIN, "name": "The Octocat" }))
+        .into_response()
+}
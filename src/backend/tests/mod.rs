@@ -0,0 +1,198 @@
+//! Integration tests for the HTTP API.
+//!
+//! These tests wire up the `/v1/` router with the same session and auth layers as the
+//! server, and point the OAuth provider at an in-process mock, so whole request flows
+//! run without reaching the network.
+mod mock_github;
+mod callback_errors;
+mod oauth_flow;
+mod redirects;
+mod server_fns;
+mod sessions;
+mod signatures;
+use crate::backend::{
+    AppState,
+    domain::logic::{
+        AuthBackend,
+        providers::{ConfiguredProvider, GitHub, Providers},
+        sessions::track_activity,
+    },
+    extractors::CookieExtractor,
+    repos::{GroupsAndPermissionsRepo, PgRepository},
+    session_keys::SESSION_COOKIE,
+    token_cipher::TokenCipher,
+    wapi::api_router,
+};
+use axum::{
+    Router,
+    body::Body,
+    http::{
+        Request, Response, StatusCode,
+        header::{ACCEPT, CONTENT_TYPE, COOKIE, LOCATION, SET_COOKIE, USER_AGENT},
+        request::Builder,
+    },
+};
+use axum_extra::extract::cookie::Key;
+use axum_login::AuthManagerLayerBuilder;
+use axum_login::tower_sessions::SessionManagerLayer;
+use dioxus::fullstack::prelude::DioxusRouterExt;
+use mock_github::MockGithub;
+use reqwest::Url;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use tower_governor::governor::GovernorConfigBuilder;
+use tower_sessions_sqlx_store::PostgresStore;
+const DOMAIN: &str = "http://localhost:8080";
+/// The user agent test requests are sent with.
+pub(crate) const TEST_USER_AGENT: &str = "gabioinf-tests";
+/// An external origin post-login redirects may point at.
+pub(crate) const ALLOWED_ORIGIN: &str = "https://gabioinf.com";
+/// The API under test together with the mock provider it talks to.
+pub(crate) struct TestApp {
+    pub router: Router,
+    pub pool: PgPool,
+    pub github: MockGithub,
+    pub cipher: TokenCipher,
+}
+impl TestApp {
+    /// Builds the API against a fresh mock GitHub.
+    pub async fn spawn(pool: PgPool) -> Self {
+        let github = MockGithub::spawn().await;
+        let reqwest_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let cipher = TokenCipher::new(&[7u8; 32]).unwrap();
+        let state = AppState::new(
+            pool.clone(),
+            DOMAIN.to_string(),
+            reqwest_client.clone(),
+            Key::generate(),
+            vec![ALLOWED_ORIGIN.to_string()],
+            3,
+        );
+        let session_store = PostgresStore::new(pool.clone());
+        session_store.migrate().await.unwrap();
+        let session_layer = SessionManagerLayer::new(session_store)
+            .with_signed(state.key.clone());
+        let providers = Providers::default()
+            .with(ConfiguredProvider::new(github.provider(), "id", "secret", DOMAIN));
+        let backend = AuthBackend::new(
+            PgRepository::new(pool.clone()),
+            GroupsAndPermissionsRepo::new(pool.clone()),
+            providers,
+            reqwest_client,
+            Default::default(),
+            cipher.clone(),
+        );
+        let auth_layer = AuthManagerLayerBuilder::new(backend, session_layer).build();
+        let governor_conf = Arc::new(
+            GovernorConfigBuilder::default()
+                .burst_size(100)
+                .key_extractor(CookieExtractor)
+                .finish()
+                .unwrap(),
+        );
+        let session_repo = state.session_repo.clone();
+        let router = Router::new()
+            .nest("/v1/", api_router(state.clone(), governor_conf))
+            .register_server_functions_with_context(
+                Arc::new(vec![Box::new(move || { Box::new(state.clone()) })]),
+            )
+            .layer(axum::middleware::from_fn_with_state(session_repo, track_activity))
+            .layer(auth_layer);
+        Self { router, pool, github, cipher }
+    }
+    /// Sends a GET request, optionally carrying a session cookie.
+    pub async fn get(&self, uri: &str, cookie: Option<&str>) -> Response<Body> {
+        self.send(Request::builder().uri(uri), cookie, Body::empty()).await
+    }
+    /// Sends a GET request that asks for a JSON response.
+    pub async fn get_json(&self, uri: &str, cookie: Option<&str>) -> Response<Body> {
+        self.send(
+                Request::builder().uri(uri).header(ACCEPT, "application/json"),
+                cookie,
+                Body::empty(),
+            )
+            .await
+    }
+    /// Calls a server function with URL encoded arguments, the way the client does.
+    pub async fn call(&self, path: &str, args: &str, cookie: Option<&str>) -> Response<Body> {
+        let request = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded");
+        self.send(request, cookie, Body::from(args.to_string())).await
+    }
+    async fn send(
+        &self,
+        request: Builder,
+        cookie: Option<&str>,
+        body: Body,
+    ) -> Response<Body> {
+        let mut request = request
+            .header("x-forwarded-for", "127.0.0.1")
+            .header(USER_AGENT, TEST_USER_AGENT);
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, cookie);
+        }
+        self.router.clone().oneshot(request.body(body).unwrap()).await.unwrap()
+    }
+}
+impl TestApp {
+    /// Starts a login and returns the CSRF state sent to the provider together with
+    /// the session cookie.
+    pub async fn start_login(&self, uri: &str) -> (String, String) {
+        let response = self.get(uri, None).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let authorize = Url::parse(&location(&response)).unwrap();
+        let state = authorize
+            .query_pairs()
+            .find(|(key, _)| key == "state")
+            .map(|(_, value)| value.into_owned())
+            .expect("authorize URL should carry a state");
+        (state, session_cookie(&response))
+    }
+    /// Runs a whole login against the mock provider and returns the callback response.
+    pub async fn complete_login(&self, uri: &str) -> Response<Body> {
+        let (state, cookie) = self.start_login(uri).await;
+        self.get(
+                &format!("/v1/oauth/callback?code={}&state={state}", mock_github::CODE),
+                Some(&cookie),
+            )
+            .await
+    }
+    /// Logs in as the mock GitHub user and returns their session cookie.
+    pub async fn login(&self) -> String {
+        let response = self.complete_login("/v1/login").await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        session_cookie(&response)
+    }
+}
+/// Returns the `Location` header of a redirect.
+pub(crate) fn location(response: &Response<Body>) -> String {
+    response.headers()[LOCATION].to_str().unwrap().to_string()
+}
+/// Reads a response body as JSON.
+pub(crate) async fn json_body(response: Response<Body>) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+/// Reads a response body as text.
+pub(crate) async fn text_body(response: Response<Body>) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+/// Returns the session cookie set by a response, as a `Cookie` header value.
+pub(crate) fn session_cookie(response: &Response<Body>) -> String {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| value.split(';').next())
+        .find(|pair| pair.starts_with(&format!("{SESSION_COOKIE}=")))
+        .expect("response should set the session cookie")
+        .to_string()
+}
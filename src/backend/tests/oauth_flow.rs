@@ -0,0 +1,88 @@
+//! The `/v1/login` → `/v1/oauth/callback` flow.
+use super::{TestApp, location, mock_github, session_cookie};
+use crate::backend::repos::{GuestCriteria, PgRepository, Repository};
+use crate::shared::models::{Guest, Provider};
+use axum::http::StatusCode;
+use reqwest::Url;
+use sqlx::PgPool;
+async fn stored_guest(pool: &PgPool) -> Option<Guest> {
+    PgRepository::<Guest>::new(pool.clone())
+        .read(&GuestCriteria::WithIdentity(Provider::Github, mock_github::USER_ID.into()))
+        .await
+        .ok()
+}
+#[sqlx::test]
+async fn test_login_redirects_to_provider(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let response = app.get("/v1/login?next=/guestbook", None).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let authorize = Url::parse(&location(&response)).unwrap();
+    assert!(authorize.as_str().starts_with(&app.github.provider().authorize));
+    let params: Vec<(String, String)> = authorize.query_pairs().into_owned().collect();
+    assert!(params.contains(&("client_id".to_string(), "id".to_string())));
+    assert!(
+        params
+            .contains(
+                &(
+                    "redirect_uri".to_string(),
+                    "http://localhost:8080/v1/oauth/callback".to_string(),
+                ),
+            )
+    );
+}
+#[sqlx::test]
+async fn test_login_callback_flow(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login?next=/guestbook").await;
+    let response = app
+        .get(
+            &format!("/v1/oauth/callback?code={}&state={state}", mock_github::CODE),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(location(&response), "/guestbook");
+    assert_ne!(session_cookie(&response), cookie);
+    let guest = stored_guest(&app.pool).await.expect("guest should be provisioned");
+    assert_eq!(guest.username, mock_github::USER_LOGIN);
+    assert_eq!(guest.name, "The Octocat");
+    assert_eq!(app.cipher.decrypt(&guest.access_token).unwrap(), mock_github::ACCESS_TOKEN);
+}
+#[sqlx::test]
+async fn test_callback_rejects_csrf_mismatch(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (_, cookie) = app.start_login("/v1/login").await;
+    let response = app
+        .get_json(
+            &format!("/v1/oauth/callback?code={}&state=forged", mock_github::CODE),
+            Some(&cookie),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(stored_guest(&app.pool).await.is_none());
+}
+#[sqlx::test]
+async fn test_callback_without_login_is_rejected(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let response = app
+        .get_json(&format!("/v1/oauth/callback?code={}&state=any", mock_github::CODE), None)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(stored_guest(&app.pool).await.is_none());
+}
+#[sqlx::test]
+async fn test_callback_with_invalid_code(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let (state, cookie) = app.start_login("/v1/login?next=/guestbook").await;
+    let response = app
+        .get_json(&format!("/v1/oauth/callback?code=wrong&state={state}"), Some(&cookie))
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert!(stored_guest(&app.pool).await.is_none());
+}
+#[sqlx::test]
+async fn test_login_with_unconfigured_provider(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let response = app.get("/v1/login?provider=gitlab", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,38 @@
+//! Validation of the `next` parameter across the login flow.
+use super::{ALLOWED_ORIGIN, TestApp, location};
+use axum::http::StatusCode;
+use sqlx::PgPool;
+async fn redirect_after_login(app: &TestApp, next: &str) -> String {
+    let response = app.complete_login(&format!("/v1/login?next={next}")).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    location(&response)
+}
+#[sqlx::test]
+async fn test_relative_next_is_kept(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    assert_eq!(redirect_after_login(&app, "%2Fguestbook").await, "/guestbook");
+}
+#[sqlx::test]
+async fn test_allowed_origin_is_kept(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let next = format!("{ALLOWED_ORIGIN}/guestbook").replace(':', "%3A").replace('/', "%2F");
+    assert_eq!(
+        redirect_after_login(&app, &next).await,
+        format!("{ALLOWED_ORIGIN}/guestbook")
+    );
+}
+#[sqlx::test]
+async fn test_bypass_attempts_fall_back_to_root(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    for next in [
+        "%2F%2Fevil.com",
+        "%2F%5Cevil.com",
+        "https%3A%2F%2Fevil.com",
+        "https%3A%2F%2Fgabioinf.com%40evil.com",
+        "%2F%252F%2Fevil.com",
+        "%2F%09%2Fevil.com",
+        "javascript%3Aalert(1)",
+    ] {
+        assert_eq!(redirect_after_login(&app, next).await, "/", "next={next}");
+    }
+}
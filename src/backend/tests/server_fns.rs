@@ -0,0 +1,60 @@
+//! Calling server functions with a real session.
+use super::{TestApp, mock_github, text_body};
+use crate::backend::repos::{GuestCriteria, GuestbookEntryCriteria, PgRepository, Repository};
+use crate::backend::utils::setup_guest;
+use crate::shared::models::{Guest, GuestbookEntry, Provider};
+use crate::shared::server_fns::{SubmitSignature, UpdateSignature};
+use axum::http::StatusCode;
+use dioxus::prelude::server_fn::ServerFn;
+use sqlx::PgPool;
+async fn logged_in_guest(pool: &PgPool) -> Guest {
+    PgRepository::<Guest>::new(pool.clone())
+        .read(&GuestCriteria::WithIdentity(Provider::Github, mock_github::USER_ID.into()))
+        .await
+        .unwrap()
+}
+#[sqlx::test]
+async fn test_submit_ignores_forged_guest(pool: PgPool) {
+    let app = TestApp::spawn(pool.clone()).await;
+    let victim = setup_guest(&pool).await;
+    let cookie = app.login().await;
+    let author = logged_in_guest(&pool).await;
+    let args = format!(
+        "payload[message]=++forged++&guest[id]={}&guest[username]={}",
+        victim.id.as_value(),
+        victim.username,
+    );
+    let response = app.call(SubmitSignature::PATH, &args, Some(&cookie)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let entries = sqlx::query!("SELECT author_id, author_username, message FROM guestbook")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].author_id, author.id.as_value());
+    assert_eq!(entries[0].author_username, author.username);
+    assert_eq!(entries[0].message, "forged");
+}
+#[sqlx::test]
+async fn test_update_rejects_foreign_entry(pool: PgPool) {
+    let app = TestApp::spawn(pool.clone()).await;
+    let other = setup_guest(&pool).await;
+    let repo = PgRepository::<GuestbookEntry>::new(pool);
+    let entry = repo
+        .create(&GuestbookEntry {
+            message: "Original".to_string(),
+            author_id: other.id,
+            author_username: other.username.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let cookie = app.login().await;
+    let args = format!("id={}&payload[message]=hijacked", entry.id.as_value());
+    let response = app.call(UpdateSignature::PATH, &args, Some(&cookie)).await;
+    assert!(!response.status().is_success());
+    let error = text_body(response).await;
+    assert!(error.contains("Authorization failed: Cannot edit"), "{error}");
+    let stored = repo.read(&GuestbookEntryCriteria::WithId(entry.id)).await.unwrap();
+    assert_eq!(stored.message, "Original");
+}
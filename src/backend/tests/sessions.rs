@@ -0,0 +1,49 @@
+//! Recording and revoking the sessions guests log in with.
+use super::{TEST_USER_AGENT, TestApp, mock_github, text_body};
+use crate::backend::repos::{GuestCriteria, PgRepository, Repository, SessionRepo};
+use crate::shared::models::{Guest, Provider};
+use crate::shared::server_fns::RevokeSession;
+use axum::http::StatusCode;
+use dioxus::prelude::server_fn::ServerFn;
+use sqlx::PgPool;
+#[sqlx::test]
+async fn test_login_records_session(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let response = app.complete_login("/v1/login?next=/guestbook").await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let guest = PgRepository::<Guest>::new(app.pool.clone())
+        .read(&GuestCriteria::WithIdentity(Provider::Github, mock_github::USER_ID.into()))
+        .await
+        .unwrap();
+    let repo = SessionRepo::new(app.pool.clone());
+    let sessions = repo.list_active(guest.id).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].user_agent.as_deref(), Some(TEST_USER_AGENT));
+    assert_eq!(sessions[0].ip.as_deref(), Some("127.0.0.0/24"));
+    assert!(repo.revoke(guest.id, sessions[0].id).await.unwrap());
+    assert!(repo.list_active(guest.id).await.unwrap().is_empty());
+    let stored = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM tower_sessions.session WHERE id = $1", sessions[0].session_id
+    )
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, Some(0));
+}
+#[sqlx::test]
+async fn test_current_session_cannot_be_revoked(pool: PgPool) {
+    let app = TestApp::spawn(pool).await;
+    let cookie = app.login().await;
+    let guest = PgRepository::<Guest>::new(app.pool.clone())
+        .read(&GuestCriteria::WithIdentity(Provider::Github, mock_github::USER_ID.into()))
+        .await
+        .unwrap();
+    let repo = SessionRepo::new(app.pool.clone());
+    let sessions = repo.list_active(guest.id).await.unwrap();
+    let args = format!("id={}", sessions[0].id);
+    let response = app.call(RevokeSession::PATH, &args, Some(&cookie)).await;
+    assert!(!response.status().is_success());
+    let error = text_body(response).await;
+    assert!(error.contains("Cannot revoke the current session"), "{error}");
+    assert_eq!(repo.list_active(guest.id).await.unwrap().len(), 1);
+}
@@ -0,0 +1,77 @@
+//! Serving signatures as standalone SVG images.
+use super::TestApp;
+use crate::backend::repos::{PgRepository, Repository};
+use crate::backend::utils::setup_guest;
+use crate::shared::models::GuestbookEntry;
+use axum::{
+    body::Body,
+    http::{
+        Request, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+    },
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+const STROKES: &str = "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|10,20,0.5;30,40,0.5;50,60,0.5";
+#[sqlx::test]
+async fn test_signature_svg(pool: PgPool) {
+    let app = TestApp::spawn(pool.clone()).await;
+    let guest = setup_guest(&pool).await;
+    let entry = PgRepository::<GuestbookEntry>::new(pool)
+        .create(&GuestbookEntry {
+            message: "Signed".to_string(),
+            signature: Some(STROKES.to_string()),
+            author_id: guest.id,
+            author_username: guest.username.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let uri = format!("/v1/signatures/{}.svg", entry.id.as_value());
+    let response = app.get(&uri, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "image/svg+xml");
+    assert_eq!(response.headers()[CACHE_CONTROL], "public, no-cache");
+    let etag = response.headers()[ETAG].clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let svg = String::from_utf8(body.to_vec()).unwrap();
+    assert!(svg.starts_with("<svg") && svg.contains("<path d=\"M"));
+    let revalidation = Request::builder()
+        .uri(&uri)
+        .header("x-forwarded-for", "127.0.0.1")
+        .header(IF_NONE_MATCH, etag)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.router.clone().oneshot(revalidation).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    for missing in ["/v1/signatures/4242.svg", "/v1/signatures/abc.svg", "/v1/signatures/1.png"] {
+        assert_eq!(app.get(missing, None).await.status(), StatusCode::NOT_FOUND, "{missing}");
+    }
+}
+#[sqlx::test]
+async fn test_signature_embeddable(pool: PgPool) {
+    let app = TestApp::spawn(pool.clone()).await;
+    let guest = setup_guest(&pool).await;
+    let entry = PgRepository::<GuestbookEntry>::new(pool)
+        .create(&GuestbookEntry {
+            message: "Embedded".to_string(),
+            signature: Some(STROKES.to_string()),
+            author_id: guest.id,
+            author_username: guest.username.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let uri = format!("/v1/signatures/{}.svg", entry.id.as_value());
+    let response = app.get(&uri, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["cross-origin-resource-policy"], "cross-origin");
+    assert_eq!(headers["content-security-policy"], "default-src 'none'; img-src data:");
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    // The rest of the API keeps the same-origin policy.
+    let response = app.get("/v1/ping", None).await;
+    assert_eq!(response.headers()["cross-origin-resource-policy"], "same-origin");
+    let csp = response.headers()["content-security-policy"].to_str().unwrap();
+    assert!(csp.contains("default-src 'self'"));
+}
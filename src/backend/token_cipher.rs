@@ -0,0 +1,104 @@
+//! Encryption of third-party access tokens at rest.
+//!
+//! Tokens are encrypted with AES-256-GCM under a key loaded from [`EncryptionConfig`]
+//! and stored as base64 encoded `nonce || ciphertext`.
+use crate::backend::{
+    config::{EncryptionConfig, RunMode},
+    utils::decode_secret,
+};
+use aes_gcm::Aes256Gcm;
+#[cfg(test)]
+use aes_gcm::Nonce;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use anyhow::{Context, anyhow};
+use base64::Engine;
+/// Length of the AES-GCM nonce prepended to every ciphertext.
+#[cfg(test)]
+const NONCE_LEN: usize = 12;
+/// Encrypts and decrypts access tokens.
+#[derive(Clone)]
+pub struct TokenCipher {
+    cipher: Aes256Gcm,
+}
+impl std::fmt::Debug for TokenCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenCipher(***)")
+    }
+}
+impl TokenCipher {
+    /// Creates a cipher from a raw 32 byte key.
+    pub fn new(key: &[u8]) -> anyhow::Result<Self> {
+        let cipher = Aes256Gcm::new_from_slice(key)
+            .map_err(|_| anyhow!("Encryption key must be 32 bytes long"))?;
+        Ok(Self { cipher })
+    }
+    /// Loads the cipher from the configuration.
+    ///
+    /// If no key is configured, a random one is generated in development, while
+    /// production refuses to start.
+    pub fn from_config(config: &EncryptionConfig, run_mode: RunMode) -> anyhow::Result<Self> {
+        match &config.key {
+            Some(secret) => Self::new(&decode_secret(secret)?).context("Invalid encryption key"),
+            None if run_mode == RunMode::Development => {
+                dioxus_logger::tracing::warn!(
+                    "No encryption key configured, stored tokens will not survive a restart"
+                );
+                Ok(Self { cipher: Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng)) })
+            }
+            None => Err(anyhow!("An encryption key must be configured in production")),
+        }
+    }
+    /// Encrypts a token for storage.
+    pub fn encrypt(&self, token: &str) -> anyhow::Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, token.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt token"))?;
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        Ok(base64::engine::general_purpose::STANDARD.encode(data))
+    }
+    /// Decrypts a token previously produced by [`TokenCipher::encrypt`].
+    ///
+    /// Stored tokens are not read back by the server yet, so this only checks them in tests.
+    #[cfg(test)]
+    pub fn decrypt(&self, stored: &str) -> anyhow::Result<String> {
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(stored)
+            .context("Stored token is not base64 encoded")?;
+        if data.len() < NONCE_LEN {
+            return Err(anyhow!("Stored token is too short"));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt token"))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_roundtrip() {
+        let cipher = TokenCipher::new(&[3u8; 32]).unwrap();
+        let stored = cipher.encrypt("gho_secret").unwrap();
+        assert!(!stored.contains("gho_secret"));
+        assert_ne!(stored, cipher.encrypt("gho_secret").unwrap());
+        assert_eq!(cipher.decrypt(&stored).unwrap(), "gho_secret");
+    }
+    #[test]
+    fn test_missing_key() {
+        let config = EncryptionConfig::default();
+        assert!(TokenCipher::from_config(&config, RunMode::Development).is_ok());
+        assert!(TokenCipher::from_config(&config, RunMode::Production).is_err());
+    }
+    #[test]
+    fn test_wrong_key_fails() {
+        let stored = TokenCipher::new(&[3u8; 32]).unwrap().encrypt("gho_secret").unwrap();
+        assert!(TokenCipher::new(&[4u8; 32]).unwrap().decrypt(&stored).is_err());
+        assert!(TokenCipher::new(&[3u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use super::validate_not_offensive;
+use validator::ValidationError;
+/// The longest guestbook message or reply, matching the `message` column.
+pub const MAX_MESSAGE_LENGTH: usize = 255;
+/// Checks the text of a guestbook message or reply.
+///
+/// Messages must be between 1 and [`MAX_MESSAGE_LENGTH`] characters long and must not be
+/// offensive. Surrounding whitespace is not counted, as it is trimmed before storing.
+pub fn validate_message<S: AsRef<str>>(text: S) -> Result<(), ValidationError> {
+    let text = text.as_ref().trim();
+    if !(1..=MAX_MESSAGE_LENGTH).contains(&text.chars().count()) {
+        return Err(
+            ValidationError::new("length")
+                .with_message(
+                    format!("Must be between 1 and {MAX_MESSAGE_LENGTH} characters").into(),
+                ),
+        );
+    }
+    validate_not_offensive(text).map_err(|e| e.with_message("watch your mouth".into()))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_validate_message() {
+        assert!(validate_message("Hello there").is_ok());
+        assert!(validate_message("ü".repeat(MAX_MESSAGE_LENGTH)).is_ok());
+        let error = validate_message("").unwrap_err();
+        assert_eq!(error.code, "length");
+        assert_eq!(validate_message(" \n\t ").unwrap_err().code, "length");
+        assert!(validate_message(format!(" {} ", "a".repeat(MAX_MESSAGE_LENGTH))).is_ok());
+        assert!(validate_message("a".repeat(MAX_MESSAGE_LENGTH + 1)).is_err());
+    }
+}
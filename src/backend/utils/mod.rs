@@ -1,5 +1,11 @@
+mod message_rules;
+pub use message_rules::*;
 mod profanity_filter;
 pub use profanity_filter::*;
+mod secrets;
+pub use secrets::*;
+mod signature_payload;
+pub use signature_payload::*;
 mod testutils;
 #[allow(unused_imports)]
 #[cfg(test)]
@@ -0,0 +1,19 @@
+use anyhow::anyhow;
+use base64::Engine;
+/// Decodes a secret configured as either hex or base64.
+pub fn decode_secret(secret: &str) -> anyhow::Result<Vec<u8>> {
+    let secret = secret.trim();
+    hex::decode(secret)
+        .or_else(|_| base64::engine::general_purpose::STANDARD.decode(secret))
+        .map_err(|_| anyhow!("Secret must be hex or base64 encoded"))
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_decode_secret() {
+        assert_eq!(decode_secret("0a0b").unwrap(), vec![10, 11]);
+        assert_eq!(decode_secret(" AQID ").unwrap(), vec![1, 2, 3]);
+        assert!(decode_secret("not a secret!").is_err());
+    }
+}
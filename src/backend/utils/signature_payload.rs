@@ -0,0 +1,148 @@
+//! Sanitizing of the signatures guests submit with their entries.
+//!
+//! A signature is either drawn strokes, see [`SignatureStrokes`], or a base64 encoded
+//! PNG. PNGs are still accepted because clients running a build from before signatures
+//! were stored as strokes submit the pad as one. Either way the signature is decoded,
+//! checked against size limits and re-encoded before it is stored, so nothing but the
+//! drawing itself reaches the database.
+use crate::shared::models::SignatureStrokes;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::borrow::Cow;
+use validator::ValidationError;
+/// The largest signature payload accepted, in bytes.
+pub const MAX_SIGNATURE_BYTES: usize = 512 * 1024;
+/// The largest width or height of a PNG signature, in pixels.
+pub const MAX_PNG_DIMENSION: u32 = 2048;
+/// The most memory the PNG decoder may use, enough for the largest accepted image.
+const MAX_PNG_DECODER_BYTES: usize = (MAX_PNG_DIMENSION * MAX_PNG_DIMENSION * 4) as usize;
+fn invalid(message: impl Into<Cow<'static, str>>) -> ValidationError {
+    ValidationError::new("signature").with_message(message.into())
+}
+/// Decodes a submitted signature, checks it and re-encodes it for storage.
+///
+/// Strokes are rounded and serialized anew. PNGs are decoded to 8-bit pixels and
+/// encoded again, dropping every chunk besides the image data.
+///
+/// # Errors
+///
+/// If the payload is too large, in neither format, malformed or blank.
+pub fn sanitize_signature(payload: &str) -> Result<String, ValidationError> {
+    if payload.len() > MAX_SIGNATURE_BYTES {
+        return Err(invalid("Signature is too large"));
+    }
+    if SignatureStrokes::is_strokes(payload) {
+        let strokes = SignatureStrokes::decode(payload).map_err(|e| invalid(e.to_string()))?;
+        return Ok(strokes.encode());
+    }
+    let png = STANDARD
+        .decode(payload)
+        .map_err(|_| invalid("Signature is neither strokes nor a PNG"))?;
+    Ok(STANDARD.encode(sanitize_png(&png)?))
+}
+/// Decodes a PNG image, checks it and encodes its pixels anew.
+fn sanitize_png(png: &[u8]) -> Result<Vec<u8>, ValidationError> {
+    let malformed = |e: png::DecodingError| invalid(format!("Malformed PNG: {e}"));
+    let limits = png::Limits {
+        bytes: MAX_PNG_DECODER_BYTES,
+    };
+    let mut decoder = png::Decoder::new_with_limits(png, limits);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(malformed)?;
+    let (width, height) = reader.info().size();
+    let dimensions = 1..=MAX_PNG_DIMENSION;
+    if !dimensions.contains(&width) || !dimensions.contains(&height) {
+        return Err(invalid("Signature image is too large"));
+    }
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut pixels).map_err(malformed)?;
+    pixels.truncate(frame.buffer_size());
+    if is_blank(&pixels, frame.color_type) {
+        return Err(invalid("Signature is blank"));
+    }
+    let unencodable = |e: png::EncodingError| invalid(format!("Cannot re-encode PNG: {e}"));
+    let mut sanitized = Vec::new();
+    let mut encoder = png::Encoder::new(&mut sanitized, width, height);
+    encoder.set_color(frame.color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(unencodable)?;
+    writer.write_image_data(&pixels).map_err(unencodable)?;
+    writer.finish().map_err(unencodable)?;
+    Ok(sanitized)
+}
+/// Whether an image is entirely transparent or a single colour.
+fn is_blank(pixels: &[u8], colour: png::ColorType) -> bool {
+    let has_alpha = matches!(
+        colour,
+        png::ColorType::GrayscaleAlpha | png::ColorType::Rgba
+    );
+    let mut pixels = pixels.chunks_exact(colour.samples());
+    let first = pixels.next().unwrap_or_default();
+    let transparent = |pixel: &[u8]| has_alpha && pixel.last() == Some(&0);
+    let all_transparent = transparent(first) && pixels.clone().all(transparent);
+    all_transparent || pixels.all(|pixel| pixel == first)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    /// Encodes an RGBA image of the given pixels with a text chunk.
+    fn rgba_png(width: u32, pixels: &[[u8; 4]]) -> Vec<u8> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width, pixels.len() as u32 / width);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .add_text_chunk("Comment".into(), "drawn at home".into())
+            .unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(pixels.as_flattened()).unwrap();
+        writer.finish().unwrap();
+        png
+    }
+    #[test]
+    fn test_sanitize_png() {
+        let ink = [242, 242, 242, 255];
+        let pixels = [[0; 4], ink, [0; 4], [0; 4]];
+        let sanitized = STANDARD
+            .decode(sanitize_signature(&STANDARD.encode(rgba_png(2, &pixels))).unwrap())
+            .unwrap();
+        let mut reader = png::Decoder::new(sanitized.as_slice()).read_info().unwrap();
+        assert!(reader.info().uncompressed_latin1_text.is_empty());
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut decoded).unwrap();
+        assert_eq!(decoded, pixels.as_flattened());
+        // The stripped image is accepted again as is.
+        assert_eq!(sanitize_png(&sanitized).unwrap(), sanitized);
+    }
+    #[test]
+    fn test_rejects_invalid_png() {
+        let transparent = rgba_png(2, &[[255, 0, 0, 0], [0; 4]]);
+        let filled = rgba_png(2, &[[242, 242, 242, 255]; 4]);
+        let wide = rgba_png(
+            MAX_PNG_DIMENSION + 1,
+            &[[0; 4]; MAX_PNG_DIMENSION as usize + 1],
+        );
+        let mut corrupt = rgba_png(2, &[[0; 4], [242, 242, 242, 255]]);
+        let last = corrupt.len() - 13;
+        corrupt[last] ^= 1;
+        for png in [transparent, filled, wide, corrupt, b"GIF89a".to_vec()] {
+            assert!(sanitize_png(&png).is_err());
+        }
+    }
+    #[test]
+    fn test_sanitize_signature() {
+        let strokes = "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|10,20,0.5;30.25,40,0.5,16";
+        assert_eq!(
+            sanitize_signature(strokes).unwrap(),
+            "v1|800,400|10.00,0.25,0.50,0.50,1,0.00,l,20.00,c|10.0,20.0,0.50;30.2,40.0,0.50,16"
+        );
+        for payload in [
+            "",
+            "not base64!",
+            "v1|garbage",
+            &"A".repeat(MAX_SIGNATURE_BYTES + 4),
+        ] {
+            assert!(sanitize_signature(payload).is_err());
+        }
+    }
+}
@@ -53,13 +53,29 @@ pub fn api_router(
     let helmet_layer = HelmetLayer::new(generate_general_helmet_headers());
     let auth_router = logic::auth::router();
     let oauth_router = logic::oauth::router();
+    // Signature images are meant to be embedded on other sites, so they are kept out of the
+    // helmet layer and set their own headers instead.
+    let signatures_router = logic::signatures::router().with_state(state.clone());
     let api_router = Router::new()
         .route("/ping", axum::routing::get(ping_db))
-        .with_state(state)
         .merge(auth_router)
         .merge(oauth_router)
-        .layer(cors);
-    Router::new().merge(api_router).layer(
+        .with_state(state)
+        .layer(cors)
+        .layer(
+            ServiceBuilder::new()
+                .layer(helmet_layer)
+                .map_response(|mut res: Response<Body>| {
+                    if res.headers().get("content-security-policy").is_none() {
+                        res.headers_mut().insert(
+                            "content-security-policy",
+                            generate_default_csp().to_string().parse().unwrap(),
+                        );
+                    }
+                    res
+                }),
+        );
+    Router::new().merge(api_router).merge(signatures_router).layer(
         ServiceBuilder::new()
             .layer(GovernorLayer {
                 config: governor_conf,
@@ -74,16 +90,6 @@ pub fn api_router(
                 ))
             }))
             .timeout(std::time::Duration::from_secs(10))
-            .layer(helmet_layer)
-            .map_response(|mut res: Response<Body>| {
-                if res.headers().get("content-security-policy").is_none() {
-                    res.headers_mut().insert(
-                        "content-security-policy",
-                        generate_default_csp().to_string().parse().unwrap(),
-                    );
-                }
-                res
-            })
             .into_inner(),
     )
 }
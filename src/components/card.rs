@@ -1,7 +1,8 @@
-use crate::shared::models::GuestbookEntry;
+use crate::components::{ReactionChips, ReportMenu, SignatureImage};
+use crate::shared::models::{GuestbookEntry, LoginProvider, ReactionCount};
 use dioxus::prelude::*;
 use serde::Deserialize;
-use time::UtcOffset;
+use time::{OffsetDateTime, UtcOffset};
 #[derive(Props, Clone, Debug, PartialEq)]
 pub struct CardProps {
     card_type: CardType,
@@ -13,6 +14,7 @@ pub enum CardType {
     Project(Project),
     Signature {
         entry: GuestbookEntry,
+        reactions: Vec<ReactionCount>,
         close_button: Element,
     },
     Skeleton,
@@ -68,42 +70,67 @@ pub fn Card(props: CardProps) -> Element {
         }
         CardType::Signature {
             entry,
+            reactions,
             close_button,
         } => {
-            let sigb64 = entry.signature.clone().unwrap_or_default();
-            let date = entry.created_at;
-            let offset = UtcOffset::local_offset_at(date).unwrap_or(date.offset());
-            let date = date
-                .to_offset(offset)
-                .format(time::macros::format_description!(
-                    "[day] [month repr:short], [year], [hour repr:24]:[minute]"
-                ))
-                .unwrap()
-                .to_string();
+            let signature = entry.signature.clone().unwrap_or_default();
+            let date = format_local(entry.created_at);
+            let reply = entry.reply.clone().zip(entry.replied_at.map(format_local));
+            let author_url = try_consume_context::<Memo<Vec<LoginProvider>>>().and_then(|providers| {
+                providers
+                    .read()
+                    .iter()
+                    .find(|p| p.kind == entry.author_provider)
+                    .and_then(|p| p.profile_url(&entry.author_username))
+            });
             rsx! {
                 div { class: "{base_class} {props.class} flex flex-col justify-between h-full relative p-6",
                     {close_button}
                     div { class: "flex=grow",
+                        if entry.hidden_at.is_some() {
+                            span { class: "inline-block mb-2 mr-1 px-2 py-0.5 text-xs rounded-full border border-coral text-coral",
+                                "Hidden pending review"
+                            }
+                        }
+                        if entry.pinned_at.is_some() {
+                            span { class: "inline-block mb-2 px-2 py-0.5 text-xs rounded-full border border-alien-green text-alien-green",
+                                "📌 Pinned"
+                            }
+                        }
                         p { class: "text-stone-100 leading-6 mt-0", "{entry.message}" }
+                        if let Some((reply, replied_at)) = reply {
+                            div { class: "mt-3 ml-2 pl-3 border-l-2 border-alien-green",
+                                p { class: "text-stone-300 text-sm leading-5", "{reply}" }
+                                p { class: "text-xs text-stone-500 mt-1", "Owner reply · {replied_at}" }
+                            }
+                        }
+                        ReactionChips { entry_id: entry.id, reactions }
+                        ReportMenu { entry_id: entry.id, author_id: entry.author_id }
                     }
                     div { class: "mt-3 flex items-center justify-between",
                         div { class: "flex flex-col justify-end h-full text-sm text-stone-400",
                             p {
                                 "by "
-                                a {
-                                    href: format!("https://github.com/{}", entry.author_username),
-                                    class: "alien-link-muted",
-                                    rel: "noopener noreferrer",
-                                    target: "_blank",
+                                if let Some(url) = author_url {
+                                    a {
+                                        href: url,
+                                        class: "alien-link-muted",
+                                        rel: "noopener noreferrer",
+                                        target: "_blank",
+                                        "{entry.author_username}"
+                                    }
+                                } else {
                                     "{entry.author_username}"
                                 }
                             }
                             p { "{date}" }
                         }
-                        img {
-                            class: "w-[200px] max-h-[175px] -mb-4 -mr-4",
-                            src: "data:image/png;base64,{sigb64}",
-                            alt: "Signature",
+                        if !signature.is_empty() {
+                            SignatureImage {
+                                class: "w-[200px] max-h-[175px] -mb-4 -mr-4",
+                                signature,
+                                replayable: true,
+                            }
                         }
                     }
                 }
@@ -145,3 +172,13 @@ pub fn ProjectLink(url: String) -> Element {
         }
     }
 }
+/// Formats a timestamp in the local timezone of the viewer.
+fn format_local(date: OffsetDateTime) -> String {
+    let offset = UtcOffset::local_offset_at(date).unwrap_or(date.offset());
+    date.to_offset(offset)
+        .format(time::macros::format_description!(
+            "[day] [month repr:short], [year], [hour repr:24]:[minute]"
+        ))
+        .unwrap()
+        .to_string()
+}
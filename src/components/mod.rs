@@ -9,7 +9,13 @@ mod card;
 pub use card::*;
 mod signature_list;
 pub use signature_list::*;
+mod search_box;
+pub use search_box::*;
+mod reactions;
+pub use reactions::*;
 mod loading;
 pub use loading::Loading;
 mod buttons;
 pub use buttons::*;
+mod report_menu;
+pub use report_menu::*;
@@ -0,0 +1,60 @@
+use crate::auth::AuthState;
+use crate::shared::{
+    models::{GuestbookId, Reaction, ReactionCount},
+    server_fns,
+};
+use dioxus::prelude::*;
+/// Reaction counts of an entry, which signed-in guests can click to toggle their own.
+#[component]
+pub fn ReactionChips(entry_id: GuestbookId, reactions: Vec<ReactionCount>) -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    // Counts returned by the server after a toggle, tagged with the entry they belong
+    // to, since the component may be reused for another entry.
+    let mut updated = use_signal(|| None::<(GuestbookId, Vec<ReactionCount>)>);
+    let signed_in = matches!(*auth_state.read(), AuthState::Authenticated(_));
+    let counts = match &*updated.read() {
+        Some((id, counts)) if *id == entry_id => counts.clone(),
+        _ => reactions,
+    };
+    let chips: Vec<(Reaction, String, bool)> = Reaction::ALL
+        .iter()
+        .map(|reaction| {
+            let current = counts.iter().find(|c| c.reaction == *reaction);
+            let count = current.map_or(0, |c| c.count);
+            let label = if count > 0 {
+                format!("{} {count}", reaction.emoji())
+            } else {
+                reaction.emoji().to_string()
+            };
+            (*reaction, label, current.is_some_and(|c| c.reacted), count)
+        })
+        .filter(|(_, _, _, count)| signed_in || *count > 0)
+        .map(|(reaction, label, reacted, _)| (reaction, label, reacted))
+        .collect();
+    rsx! {
+        div { class: "flex flex-wrap gap-1 mt-3",
+            for (reaction , label , reacted) in chips {
+                button {
+                    class: "px-2 py-0.5 rounded-full text-sm border transition-colors duration-200",
+                    class: if reacted { "border-alien-green text-stone-100" } else { "border-onyx text-stone-400" },
+                    class: if signed_in { "hover:border-alien-green" } else { "cursor-default" },
+                    disabled: !signed_in,
+                    onclick: move |_| {
+                        spawn(async move {
+                            let result = if reacted {
+                                server_fns::remove_reaction(entry_id, reaction).await
+                            } else {
+                                server_fns::add_reaction(entry_id, reaction).await
+                            };
+                            match result {
+                                Ok(counts) => updated.set(Some((entry_id, counts))),
+                                Err(e) => dioxus_logger::tracing::error!("Error reacting: {e}"),
+                            }
+                        });
+                    },
+                    "{label}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::auth::AuthState;
+use crate::shared::{
+    models::{GuestId, GuestbookId, ReportReason},
+    server_fns,
+};
+use dioxus::prelude::*;
+/// Lets signed-in guests report an entry written by someone else.
+#[component]
+pub fn ReportMenu(entry_id: GuestbookId, author_id: GuestId) -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    let mut open = use_signal(|| false);
+    // The entry reported last, since the component may be reused for another entry.
+    let mut reported = use_signal(|| None::<GuestbookId>);
+    let can_report = match &*auth_state.read() {
+        AuthState::Authenticated(user) => !user.naughty && user.guest.id != author_id,
+        _ => false,
+    };
+    if !can_report {
+        return rsx! {};
+    }
+    if reported() == Some(entry_id) {
+        return rsx! {
+            p { class: "text-xs text-stone-500 mt-2", "Reported, thank you." }
+        };
+    }
+    rsx! {
+        div { class: "flex flex-wrap items-center gap-1 mt-2 text-xs",
+            button {
+                class: "text-stone-500 hover:text-coral transition-colors duration-200",
+                onclick: move |_| open.toggle(),
+                "Report"
+            }
+            if open() {
+                for reason in ReportReason::ALL {
+                    button {
+                        class: "px-2 py-0.5 rounded-full border border-onyx text-stone-400 hover:border-coral transition-colors duration-200",
+                        onclick: move |_| {
+                            spawn(async move {
+                                match server_fns::report_signature(entry_id, reason).await {
+                                    Ok(()) => {
+                                        open.set(false);
+                                        reported.set(Some(entry_id));
+                                    }
+                                    Err(e) => dioxus_logger::tracing::error!("Error reporting: {e}"),
+                                }
+                            });
+                        },
+                        "{reason.label()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::components::{ButtonVariant, StyledButton};
+use crate::shared::models::SignatureSearch;
+use dioxus::prelude::*;
+use time::{Date, Month};
+const INPUT_CLASS: &str = "p-2 placeholder:italic placeholder:text-[#434343] rounded-md bg-jet text-stone-100 border border-onyx focus:border-alien-green focus:outline-none";
+/// Inputs for searching the guestbook by message, author and date.
+///
+/// `on_search` is called with the filters when the search is submitted or cleared.
+#[component]
+pub fn SearchBox(on_search: EventHandler<SignatureSearch>) -> Element {
+    let mut text = use_signal(String::new);
+    let mut author = use_signal(String::new);
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+    let submit = move || {
+        on_search.call(SignatureSearch {
+            text: text(),
+            author: author(),
+            from: parse_date(&from()),
+            to: parse_date(&to()),
+        });
+    };
+    let on_enter = move |evt: KeyboardEvent| {
+        if evt.key() == Key::Enter {
+            submit();
+        }
+    };
+    rsx! {
+        div { class: "mb-6 flex flex-wrap gap-2 items-center",
+            input {
+                class: "{INPUT_CLASS} grow",
+                r#type: "search",
+                placeholder: "search messages...",
+                maxlength: "100",
+                value: "{text}",
+                oninput: move |evt| text.set(evt.value()),
+                onkeydown: on_enter,
+            }
+            input {
+                class: "{INPUT_CLASS}",
+                r#type: "text",
+                placeholder: "by username",
+                value: "{author}",
+                oninput: move |evt| author.set(evt.value()),
+                onkeydown: on_enter,
+            }
+            input {
+                class: "{INPUT_CLASS}",
+                r#type: "date",
+                aria_label: "written on or after",
+                value: "{from}",
+                oninput: move |evt| from.set(evt.value()),
+            }
+            input {
+                class: "{INPUT_CLASS}",
+                r#type: "date",
+                aria_label: "written on or before",
+                value: "{to}",
+                oninput: move |evt| to.set(evt.value()),
+            }
+            StyledButton {
+                text: "Search",
+                variant: ButtonVariant::Secondary,
+                onclick: move |_| submit(),
+            }
+            StyledButton {
+                text: "Clear",
+                variant: ButtonVariant::Secondary,
+                onclick: move |_| {
+                    text.set(String::new());
+                    author.set(String::new());
+                    from.set(String::new());
+                    to.set(String::new());
+                    on_search.call(SignatureSearch::default());
+                },
+            }
+        }
+    }
+}
+/// Parses the `YYYY-MM-DD` value of a date input.
+fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
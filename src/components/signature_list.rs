@@ -1,6 +1,9 @@
 use crate::auth::AuthState;
 use crate::components::{Card, CardType, CloseButton, Loading};
-use crate::shared::{models::GuestbookEntry, server_fns};
+use crate::shared::{
+    models::{Cursor, EntryWithReactions, GuestbookEntry, SignatureSearch},
+    server_fns,
+};
 use dioxus::prelude::*;
 
 const SIGNATURES_PER_PAGE: usize = 10;
@@ -11,7 +14,7 @@ pub enum SignatureListState {
     Initial,
     Loading(MaybeFirst),
     Finished,
-    MoreAvailable(u32),
+    MoreAvailable(Cursor),
 }
 
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
@@ -21,13 +24,15 @@ pub enum MaybeFirst {
     NotFirst,
 }
 
+/// Infinitely scrolling list of the guestbook entries matching `search`.
 #[component]
-pub fn SignatureList() -> Element {
+pub fn SignatureList(search: ReadOnlySignal<SignatureSearch>) -> Element {
     let mut load_state = use_signal(SignatureListState::default);
     let mut auth_state = use_context::<Signal<AuthState>>();
     let mut endless_signatures = use_signal(std::vec::Vec::new);
-    let load_next_batch = use_signature_list(load_state, endless_signatures);
+    let load_next_batch = use_signature_list(load_state, endless_signatures, search);
     let mut is_intersecting = use_signal(|| false);
+    let mut applied_search = use_signal(|| search.peek().clone());
 
     let mut update_user_signature = move |new_entry: Option<GuestbookEntry>| {
         let state_: &mut AuthState = &mut auth_state.write();
@@ -44,14 +49,33 @@ pub fn SignatureList() -> Element {
         }
     });
 
+    use_effect(move || {
+        let current = search();
+        if current != *applied_search.peek() {
+            applied_search.set(current);
+            endless_signatures.write().clear();
+            load_state.set(SignatureListState::Initial);
+            load_next_batch.send(());
+        }
+    });
+
     rsx! {
         div {
             {
                 let state_ = auth_state.read();
+                // The guest's own entry is pinned to the top, unless they are searching.
+                let searching = !search.read().is_empty();
                 if let AuthState::Authenticated(user_state) = &*state_ {
-                    if let Some(user_entry) = &user_state.entry {
+                    if let Some(user_entry) = user_state.entry.as_ref().filter(|_| !searching) {
                         let user_entry_id = user_entry.id;
                         let user_entry_clone = user_entry.clone();
+                        let user_reactions = endless_signatures
+                            .read()
+                            .iter()
+                            .flatten()
+                            .find(|e: &&EntryWithReactions| e.entry.id == user_entry_id)
+                            .map(|e| e.reactions.clone())
+                            .unwrap_or_default();
                         match *load_state.read() {
                             SignatureListState::Initial
                             | SignatureListState::Loading(MaybeFirst::First) => {
@@ -63,6 +87,7 @@ pub fn SignatureList() -> Element {
                                         Card {
                                             card_type: CardType::Signature {
                                                 entry: user_entry_clone.clone(),
+                                                reactions: user_reactions,
                                                 close_button: rsx! {
                                                     CloseButton {
                                                         layout: "absolute top-2 right-2 w-6 h-6",
@@ -85,10 +110,11 @@ pub fn SignatureList() -> Element {
                                                 },
                                             },
                                         }
-                                        for entry in endless_signatures.read().iter().flatten().filter(|entry| entry.id != user_entry_id) {
+                                        for item in endless_signatures.read().iter().flatten().filter(|item| item.entry.id != user_entry_id) {
                                             Card {
                                                 card_type: CardType::Signature {
-                                                    entry: entry.clone(),
+                                                    entry: item.entry.clone(),
+                                                    reactions: item.reactions.clone(),
                                                     close_button: rsx! {},
                                                 },
                                             }
@@ -100,10 +126,11 @@ pub fn SignatureList() -> Element {
                     } else {
                         rsx! {
                             div { class: "grid grid-cols-1 md:grid-cols-2 gap-6",
-                                for entry in endless_signatures.read().iter().flatten() {
+                                for item in endless_signatures.read().iter().flatten() {
                                     Card {
                                         card_type: CardType::Signature {
-                                            entry: entry.clone(),
+                                            entry: item.entry.clone(),
+                                            reactions: item.reactions.clone(),
                                             close_button: rsx! {},
                                         },
                                     }
@@ -114,10 +141,11 @@ pub fn SignatureList() -> Element {
                 } else {
                     rsx! {
                         div { class: "grid grid-cols-1 md:grid-cols-2 gap-6",
-                            for entry in endless_signatures.read().iter().flatten() {
+                            for item in endless_signatures.read().iter().flatten() {
                                 Card {
                                     card_type: CardType::Signature {
-                                        entry: entry.clone(),
+                                        entry: item.entry.clone(),
+                                        reactions: item.reactions.clone(),
                                         close_button: rsx! {},
                                     },
                                 }
@@ -161,29 +189,37 @@ pub fn SignatureList() -> Element {
 
 fn use_signature_list(
     mut state: Signal<SignatureListState>,
-    mut batches: Signal<Vec<Vec<GuestbookEntry>>>,
+    mut batches: Signal<Vec<Vec<EntryWithReactions>>>,
+    search: ReadOnlySignal<SignatureSearch>,
 ) -> Coroutine<()> {
     use futures::StreamExt as _;
-    let load_task = use_coroutine(move |mut rx: UnboundedReceiver<Option<u32>>| async move {
-        while let Some(next_cursor) = rx.next().await {
-            dioxus_logger::tracing::debug!("Loading signatures with cursor {next_cursor:?}");
+    let load_task = use_coroutine(move |mut rx: UnboundedReceiver<Option<Cursor>>| async move {
+        while let Some(cursor) = rx.next().await {
+            dioxus_logger::tracing::debug!("Loading signatures after {cursor:?}");
             let original_state = *state.read();
-            state.set(SignatureListState::Loading(if next_cursor.is_some() {
+            state.set(SignatureListState::Loading(if cursor.is_some() {
                 MaybeFirst::NotFirst
             } else {
                 MaybeFirst::First
             }));
-            match server_fns::load_signatures(next_cursor.unwrap_or(1), SIGNATURES_PER_PAGE).await {
-                Ok(signatures) => {
-                    if signatures.is_empty() {
-                        state.set(SignatureListState::Finished);
-                    } else if signatures.len() < SIGNATURES_PER_PAGE {
-                        state.set(SignatureListState::Finished);
-                        batches.write().push(signatures);
-                    } else {
-                        let next_page = next_cursor.map_or(2, |c| c + 1);
-                        state.set(SignatureListState::MoreAvailable(next_page));
-                        batches.write().push(signatures);
+            let search = search.peek().clone();
+            let page = if search.is_empty() {
+                server_fns::load_signatures(cursor, SIGNATURES_PER_PAGE).await
+            } else {
+                server_fns::search_signatures(search, cursor, SIGNATURES_PER_PAGE).await
+            };
+            match page {
+                Ok(page) => {
+                    state.set(match page.next_cursor {
+                        Some(next) => SignatureListState::MoreAvailable(next),
+                        None => SignatureListState::Finished,
+                    });
+                    // A first page replaces whatever an earlier search left behind.
+                    if cursor.is_none() {
+                        batches.write().clear();
+                    }
+                    if !page.items.is_empty() {
+                        batches.write().push(page.items);
                     }
                 }
                 Err(error) => {
@@ -1,8 +1,7 @@
-use super::{
-    point::Point, stroke::{get_stroke, CapOptions, StrokeOptions},
-    utils::get_svg_path_from_stroke,
+use crate::shared::{
+    models::{Brush, SignatureStrokes, Stroke, StrokeSample, StrokeStyle, MAX_DURATION_MS},
+    stroke::{outline_path, Point, PointExt, StrokeOptions},
 };
-use crate::components::signature_pad::utils::PointExt;
 use dioxus::prelude::*;
 use std::cell::RefCell;
 use web_sys::wasm_bindgen::JsCast;
@@ -14,29 +13,22 @@ pub struct Canvas {
     current_canvas_width: RefCell<u32>,
     current_canvas_height: RefCell<u32>,
     is_pressed: RefCell<bool>,
-    lines: RefCell<Vec<Vec<Point>>>,
-    current_line: RefCell<Vec<Point>>,
-    stroke_options: StrokeOptions,
+    lines: RefCell<Vec<Stroke>>,
+    /// Strokes taken back by undo, most recent last, until a new stroke is drawn.
+    redo_stack: RefCell<Vec<Stroke>>,
+    current_line: RefCell<Vec<StrokeSample>>,
+    /// The brush new strokes are drawn with.
+    brush: RefCell<Brush>,
+    /// When the first point of the signature was drawn, which point times count from.
+    started_at: RefCell<Option<instant::Instant>>,
+    style: StrokeStyle,
 }
 impl Canvas {
     pub fn new(canvas: HtmlCanvasElement) -> Self {
         let rect = canvas.get_bounding_client_rect();
         let current_canvas_width = RefCell::new((rect.width() * DPI) as u32);
         let current_canvas_height = RefCell::new((rect.height() * DPI) as u32);
-        let size = (rect.width() * DPI).min(rect.height() * DPI) * 0.025;
-        let stroke_options = StrokeOptions {
-            size,
-            start: CapOptions {
-                easing: |t| t,
-                ..Default::default()
-            },
-            end: CapOptions {
-                taper: Some(size * 2.),
-                easing: |t| (t - 1.0).powi(3) + 1.0,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+        let style = StrokeStyle::for_pad(rect.width() * DPI, rect.height() * DPI);
         canvas.set_width(*current_canvas_width.borrow());
         canvas.set_height(*current_canvas_height.borrow());
         Self {
@@ -45,8 +37,11 @@ impl Canvas {
             current_canvas_height,
             is_pressed: RefCell::new(false),
             lines: RefCell::new(Vec::new()),
+            redo_stack: RefCell::new(Vec::new()),
             current_line: RefCell::new(Vec::new()),
-            stroke_options,
+            brush: RefCell::new(Brush::default()),
+            started_at: RefCell::new(None),
+            style,
         }
     }
     pub fn get_context(&self) -> CanvasRenderingContext2d {
@@ -72,10 +67,25 @@ impl Canvas {
         self.beautify();
         ctx.put_image_data(&image_data, 0.0, 0.0).unwrap();
     }
+    /// The point of an event, timed since the first point of the signature.
+    fn sample(&self, event: &PointerEvent) -> StrokeSample {
+        let point = Point::from_event(event, &self.canvas);
+        let started_at = *self
+            .started_at
+            .borrow_mut()
+            .get_or_insert_with(instant::Instant::now);
+        let time = u32::try_from(started_at.elapsed().as_millis()).unwrap_or(u32::MAX);
+        StrokeSample {
+            x: point.x,
+            y: point.y,
+            pressure: point.pressure,
+            time: Some(time),
+        }
+    }
     pub fn on_mouse_down(&self, event: &PointerEvent) {
         *self.is_pressed.borrow_mut() = true;
-        let point = Point::from_event(event, &self.canvas);
-        self.current_line.borrow_mut().push(point);
+        let sample = self.sample(event);
+        self.current_line.borrow_mut().push(sample);
         self.draw_lines();
     }
     pub fn on_mouse_move(&self, event: &PointerEvent) {
@@ -84,9 +94,9 @@ impl Canvas {
         }
         let mut nextpoint = None;
         if let Some(last_point) = self.current_line.borrow().last() {
-            let point = Point::from_event(event, &self.canvas);
-            if point.dist(*last_point) > 5.0 {
-                nextpoint = Some(point);
+            let sample = self.sample(event);
+            if Point::from(sample).dist(Point::from(*last_point)) > 5.0 {
+                nextpoint = Some(sample);
             }
         }
         if let Some(nextpoint) = nextpoint {
@@ -95,11 +105,17 @@ impl Canvas {
         }
     }
     pub fn on_mouse_up(&self, event: &PointerEvent) {
-        *self.is_pressed.borrow_mut() = false;
-        let point = Point::from_event(event, &self.canvas);
-        self.current_line.borrow_mut().push(point);
-        self.lines.borrow_mut().push(self.current_line.borrow().clone());
-        self.current_line.borrow_mut().clear();
+        if !self.is_pressed.replace(false) {
+            return;
+        }
+        let sample = self.sample(event);
+        self.current_line.borrow_mut().push(sample);
+        let points = std::mem::take(&mut *self.current_line.borrow_mut());
+        self.lines.borrow_mut().push(Stroke {
+            brush: self.brush(),
+            points,
+        });
+        self.redo_stack.borrow_mut().clear();
         self.draw_lines()
     }
     pub fn beautify(&self) {
@@ -110,8 +126,6 @@ impl Canvas {
             self.canvas.width() as f64,
             self.canvas.height() as f64,
         );
-        ctx.set_stroke_style_str("#f2f2f2");
-        ctx.set_fill_style_str("#f2f2f2");
         ctx.set_image_smoothing_enabled(true);
         ctx.translate(0.5, 0.5).unwrap();
         self.draw_lines();
@@ -132,7 +146,9 @@ impl Canvas {
             .unwrap();
         ctx.put_image_data(&empty_image, 0.0, 0.0).unwrap();
         self.lines.borrow_mut().clear();
+        self.redo_stack.borrow_mut().clear();
         self.current_line.borrow_mut().clear();
+        *self.started_at.borrow_mut() = None;
     }
     pub fn undo(&self) {
         if self.lines.borrow().is_empty() {
@@ -145,73 +161,60 @@ impl Canvas {
             self.canvas.width() as f64,
             self.canvas.height() as f64,
         );
-        self.lines.borrow_mut().pop();
+        let stroke = self.lines.borrow_mut().pop();
+        self.redo_stack.borrow_mut().extend(stroke);
         self.current_line.borrow_mut().clear();
         self.draw_lines();
     }
-    pub fn get_signature_data(&self) -> String {
-        let data_url = self.canvas.to_data_url().unwrap();
-        data_url.split(',').nth(1).unwrap_or("").to_string()
+    /// Draws the stroke last taken back by [`Canvas::undo`] again.
+    pub fn redo(&self) {
+        let Some(stroke) = self.redo_stack.borrow_mut().pop() else {
+            return;
+        };
+        self.lines.borrow_mut().push(stroke);
+        self.draw_lines();
     }
-    pub fn trim_to_image(&self) -> String {
-        let ctx = self.get_context();
-        let image_data = ctx
-            .get_image_data(
-                0.0,
-                0.0,
-                self.canvas.width() as f64,
-                self.canvas.height() as f64,
-            )
-            .unwrap();
-        let data = image_data.data();
-        let mut pix_x = Vec::new();
-        let mut pix_y = Vec::new();
-        for y in 0..self.canvas.height() {
-            for x in 0..self.canvas.width() {
-                let index = ((y * self.canvas.width() + x) * 4) as usize;
-                if data[index + 3] > 0 {
-                    pix_x.push(x);
-                    pix_y.push(y);
-                }
-            }
-        }
-        if pix_x.is_empty() || pix_y.is_empty() {
-            return self.get_signature_data();
-        }
-        pix_x.sort_unstable();
-        pix_y.sort_unstable();
-        let x_min = *pix_x.first().unwrap();
-        let y_min = *pix_y.first().unwrap();
-        let x_max = *pix_x.last().unwrap();
-        let y_max = *pix_y.last().unwrap();
-        let new_w = 1 + x_max - x_min;
-        let new_h = 1 + y_max - y_min;
-        if (self.canvas.width() * self.canvas.height()) as f32 * 0.5
-            > (new_w * new_h) as f32
-        {
-            return self.get_signature_data();
+    pub fn is_empty(&self) -> bool {
+        self.lines.borrow().is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.borrow().is_empty()
+    }
+    pub fn brush(&self) -> Brush {
+        *self.brush.borrow()
+    }
+    /// Sets the brush the next strokes are drawn with.
+    pub fn set_brush(&self, brush: Brush) {
+        *self.brush.borrow_mut() = brush;
+    }
+    /// The strokes drawn so far with the times of their points, clamped to the canvas and
+    /// the longest a signature may take.
+    pub fn to_strokes(&self) -> SignatureStrokes {
+        let (width, height) = (self.canvas.width(), self.canvas.height());
+        let lines = self
+            .lines
+            .borrow()
+            .iter()
+            .map(|stroke| Stroke {
+                brush: stroke.brush,
+                points: stroke
+                    .points
+                    .iter()
+                    .map(|p| StrokeSample {
+                        x: p.x.clamp(0.0, width as f64),
+                        y: p.y.clamp(0.0, height as f64),
+                        pressure: p.pressure.clamp(0.0, 1.0),
+                        time: p.time.map(|time| time.min(MAX_DURATION_MS)),
+                    })
+                    .collect(),
+            })
+            .collect();
+        SignatureStrokes {
+            width,
+            height,
+            style: self.style,
+            lines,
         }
-        let cut = ctx
-            .get_image_data(x_min as f64, y_min as f64, new_w as f64, new_h as f64)
-            .unwrap();
-        let tmp_canvas = web_sys::window()
-            .unwrap()
-            .document()
-            .unwrap()
-            .create_element("canvas")
-            .unwrap()
-            .dyn_into::<HtmlCanvasElement>()
-            .unwrap();
-        tmp_canvas.set_width(new_w);
-        tmp_canvas.set_height(new_h);
-        let tmp_ctx = tmp_canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<CanvasRenderingContext2d>()
-            .unwrap();
-        tmp_ctx.put_image_data(&cut, 0.0, 0.0).unwrap();
-        tmp_canvas.to_data_url().unwrap().split(',').nth(1).unwrap_or("").to_string()
     }
     fn draw_lines(&self) {
         let ctx = self.get_context();
@@ -221,18 +224,17 @@ impl Canvas {
             self.canvas.width() as f64,
             self.canvas.height() as f64,
         );
-        for line in self
-            .lines
-            .borrow()
-            .iter()
-            .chain(std::iter::once(&self.current_line.borrow().to_vec()))
-        {
-            if !line.is_empty() {
-                let stroke = get_stroke(line, &self.stroke_options)
-                    .into_iter()
-                    .map(|p| p.as_vector())
-                    .collect::<Vec<_>>();
-                let path = get_svg_path_from_stroke(stroke, false);
+        let current = Stroke {
+            brush: self.brush(),
+            points: self.current_line.borrow().to_vec(),
+        };
+        for stroke in self.lines.borrow().iter().chain(std::iter::once(&current)) {
+            if !stroke.points.is_empty() {
+                let options = StrokeOptions::from(&self.style.with_brush(stroke.brush));
+                let points: Vec<Point> =
+                    stroke.points.iter().copied().map(Point::from).collect();
+                let path = outline_path(&points, &options);
+                ctx.set_fill_style_str(stroke.brush.colour());
                 ctx.fill_with_path_2d(
                     &web_sys::Path2d::new_with_path_string(&path).unwrap(),
                 );
@@ -1,3 +1,4 @@
+use crate::shared::models::{Brush, BRUSH_SIZES, STROKE_COLOURS};
 use canvas::Canvas;
 use dioxus::prelude::*;
 use dioxus::web::WebEventExt;
@@ -7,8 +8,10 @@ mod canvas;
 mod point;
 mod popup;
 pub use popup::SignaturePopup;
-mod stroke;
-mod utils;
+mod view;
+pub use view::SignatureImage;
+/// What the brush sizes are called, from thinnest to thickest.
+const BRUSH_LABELS: [&str; BRUSH_SIZES.len()] = ["Thin brush", "Medium brush", "Thick brush"];
 #[derive(Props, PartialEq, Debug, Clone)]
 pub struct SignaturePadProps {
     #[props(default)]
@@ -18,6 +21,8 @@ pub struct SignaturePadProps {
     #[props(default)]
     disabled: bool,
     #[props(default)]
+    /// Called with the serialized strokes after every stroke, undo, redo and clear, or
+    /// `None` once the pad is empty.
     on_change: Option<EventHandler<Option<String>>>,
     #[props(default)]
     on_canvas_ready: Option<EventHandler<Canvas>>,
@@ -25,6 +30,9 @@ pub struct SignaturePadProps {
 #[component]
 pub fn SignaturePad(props: SignaturePadProps) -> Element {
     let mut canvas = use_signal(|| None::<Canvas>);
+    let mut brush = use_signal(Brush::default);
+    let mut can_undo = use_signal(|| false);
+    let mut can_redo = use_signal(|| false);
     let set_canvas = use_callback(move |event: MountedEvent| {
         let html_canvas = event
             .as_web_event()
@@ -38,14 +46,47 @@ pub fn SignaturePad(props: SignaturePadProps) -> Element {
             on_canvas_ready.call(canvas_ref.clone());
         }
     });
-    let on_signature_change = move || {
+    let mut on_signature_change = move || {
         if let Some(c) = canvas.read().as_ref() {
-            let signature_data = c.get_signature_data();
+            can_undo.set(!c.is_empty());
+            can_redo.set(c.can_redo());
+            let signature_data = (!c.is_empty()).then(|| c.to_strokes().encode());
             if let Some(on_change) = &props.on_change {
-                on_change.call(Some(signature_data));
+                on_change.call(signature_data);
             }
         }
     };
+    let mut undo = move || {
+        if let Some(c) = canvas.read().as_ref() {
+            c.undo();
+        }
+        on_signature_change();
+    };
+    let mut redo = move || {
+        if let Some(c) = canvas.read().as_ref() {
+            c.redo();
+        }
+        on_signature_change();
+    };
+    let mut set_brush = move |new_brush: Brush| {
+        if let Some(c) = canvas.read().as_ref() {
+            c.set_brush(new_brush);
+        }
+        brush.set(new_brush);
+    };
+    // Ctrl+Z undoes and Ctrl+Shift+Z redoes while the pad has focus, or Cmd on macOS.
+    let on_key_down = move |event: KeyboardEvent| {
+        let modifiers = event.modifiers();
+        if !(modifiers.ctrl() || modifiers.meta()) || event.code() != Code::KeyZ {
+            return;
+        }
+        event.prevent_default();
+        if modifiers.shift() {
+            redo();
+        } else {
+            undo();
+        }
+    };
     let on_pointer_down = move |event: PointerEvent| {
         if let Some(c) = canvas.read().as_ref() {
             c.on_mouse_down(&event);
@@ -70,10 +111,12 @@ pub fn SignaturePad(props: SignaturePadProps) -> Element {
     rsx! {
         div {
             class: format!(
-                "relative block {} {}",
+                "relative block outline-none {} {}",
                 props.container_class,
                 if props.disabled { "pointer-events-none opacity-50" } else { "" },
             ),
+            tabindex: 0,
+            onkeydown: on_key_down,
             canvas {
                 onmounted: move |evt| set_canvas.call(evt),
                 class: format!("relative block {}", props.class),
@@ -83,18 +126,52 @@ pub fn SignaturePad(props: SignaturePadProps) -> Element {
                 onpointerup: on_pointer_up,
                 onresize: on_resize,
             }
+            div { class: "absolute top-4 left-4 flex items-center gap-2",
+                for (colour , value) in STROKE_COLOURS.iter().enumerate() {
+                    button {
+                        class: format!(
+                            "w-5 h-5 rounded-full border-2 {}",
+                            if brush().colour == colour { "border-stone-300" } else { "border-jet" },
+                        ),
+                        style: "background-color: {value}",
+                        r#type: "button",
+                        "aria-label": "Colour {value}",
+                        onclick: move |_| set_brush(Brush { colour, ..brush() }),
+                    }
+                }
+                for (size , (scale , label)) in BRUSH_SIZES.iter().zip(BRUSH_LABELS).enumerate() {
+                    button {
+                        class: format!(
+                            "w-6 h-6 flex items-center justify-center rounded-md {}",
+                            if brush().size == size { "bg-onyx" } else { "bg-jet" },
+                        ),
+                        r#type: "button",
+                        "aria-label": label,
+                        onclick: move |_| set_brush(Brush { size, ..brush() }),
+                        span {
+                            class: "block rounded-full bg-stone-300",
+                            style: format!("width: {0}px; height: {0}px", scale * 8.0),
+                        }
+                    }
+                }
+            }
             div { class: "absolute bottom-4 left-4 flex gap-2",
                 button {
-                    class: "font-sans text-sm bg-jet text-stone-300 px-2 py-1 rounded-md",
+                    class: "font-sans text-sm bg-jet text-stone-300 px-2 py-1 rounded-md disabled:opacity-50",
                     r#type: "button",
-                    onclick: move |_| {
-                        if let Some(c) = canvas.read().as_ref() {
-                            c.undo();
-                            on_signature_change();
-                        }
-                    },
+                    title: "Undo (Ctrl+Z)",
+                    disabled: !can_undo(),
+                    onclick: move |_| undo(),
                     "Undo"
                 }
+                button {
+                    class: "font-sans text-sm bg-jet text-stone-300 px-2 py-1 rounded-md disabled:opacity-50",
+                    r#type: "button",
+                    title: "Redo (Ctrl+Shift+Z)",
+                    disabled: !can_redo(),
+                    onclick: move |_| redo(),
+                    "Redo"
+                }
             }
             div { class: "absolute bottom-4 right-4 flex gap-2",
                 button {
@@ -103,8 +180,8 @@ pub fn SignaturePad(props: SignaturePadProps) -> Element {
                     onclick: move |_| {
                         if let Some(c) = canvas.read().as_ref() {
                             c.clear();
-                            on_signature_change();
                         }
+                        on_signature_change();
                     },
                     "Clear"
                 }
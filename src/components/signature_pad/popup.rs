@@ -1,5 +1,5 @@
 use crate::{
-    components::{signature_pad::Canvas, ButtonVariant, SignaturePad, StyledButton},
+    components::{ButtonVariant, SignaturePad, StyledButton},
     MessageValid,
 };
 use dioxus::prelude::*;
@@ -8,22 +8,34 @@ const MAX_MESSAGE_LENGTH: usize = 255;
 pub struct SignaturePopupProps {
     on_close: EventHandler<()>,
     on_submit: EventHandler<(String, String)>,
+    /// Whether the popup edits an existing entry instead of creating a new one.
+    /// When editing, an untouched pad keeps the stored signature.
+    #[props(default)]
+    editing: bool,
+    /// Message to prefill the text area with.
+    #[props(default)]
+    initial_message: String,
 }
 #[component]
 pub fn SignaturePopup(props: SignaturePopupProps) -> Element {
-    let message = use_signal(String::new);
+    let editing = props.editing;
+    let submit_label = if editing { "Update" } else { "Sign" };
+    let initial_message = props.initial_message.clone();
+    let message = use_signal(|| initial_message);
     let mut local_signature = use_signal(String::new);
-    let mut canvas_ref = use_signal(|| None::<Canvas>);
     let trim_on_submit = use_callback(move |_| {
-        if let Some(canvas) = canvas_ref.read().as_ref() {
-            let trimmed_signature = canvas.trim_to_image();
-            props.on_submit.call((message(), trimmed_signature));
-        }
+        props.on_submit.call((message(), local_signature()));
     });
     rsx! {
         div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
             div { class: "bg-nasty-black rounded-lg p-6 sm:max-w-lg w-full min-w-0 border border-onyx shadow-lg",
-                h2 { class: "text-xl font-bold mb-4 text-stone-100", "Sign guestbook" }
+                h2 { class: "text-xl font-bold mb-4 text-stone-100",
+                    if editing {
+                        "Edit signature"
+                    } else {
+                        "Sign guestbook"
+                    }
+                }
                 form {
                     class: "space-y-4",
                     onsubmit: move |evt| evt.prevent_default(),
@@ -31,7 +43,13 @@ pub fn SignaturePopup(props: SignaturePopupProps) -> Element {
                         TextArea { message }
                     }
                     div {
-                        label { class: "block text-stone-400 mb-2", "sign here" }
+                        label { class: "block text-stone-400 mb-2",
+                            if editing {
+                                "redraw your signature, or leave empty to keep it"
+                            } else {
+                                "sign here"
+                            }
+                        }
                         SignaturePad {
                             class: "border bg-jet border-onyx w-full h-48 rounded-md",
                             container_class: "w-full",
@@ -39,9 +57,6 @@ pub fn SignaturePopup(props: SignaturePopupProps) -> Element {
                             on_change: move |value: Option<String>| {
                                 local_signature.set(value.unwrap_or_default());
                             },
-                            on_canvas_ready: move |canvas: Canvas| {
-                                canvas_ref.set(Some(canvas));
-                            },
                         }
                     }
                     div { class: "flex justify-end space-x-4",
@@ -51,7 +66,7 @@ pub fn SignaturePopup(props: SignaturePopupProps) -> Element {
                             onclick: move |_| props.on_close.call(()),
                         }
                         StyledButton {
-                            text: "Sign",
+                            text: "{submit_label}",
                             variant: ButtonVariant::Primary,
                             onclick: trim_on_submit,
                         }
@@ -63,7 +78,7 @@ pub fn SignaturePopup(props: SignaturePopupProps) -> Element {
 }
 #[component]
 fn TextArea(mut message: Signal<String>) -> Element {
-    let mut char_count = use_signal(|| 0);
+    let mut char_count = use_signal(|| message.peek().chars().count());
     let mut message_valid = use_context::<Signal<MessageValid>>();
     let update_message = move |evt: Event<FormData>| {
         let new_message = evt.value();
@@ -94,6 +109,7 @@ fn TextArea(mut message: Signal<String>) -> Element {
                 placeholder: "wow, you are the coolest dude i have ever seen...",
                 rows: "3",
                 maxlength: MAX_MESSAGE_LENGTH.to_string(),
+                value: "{message}",
                 oninput: update_message,
             }
             span {
@@ -0,0 +1,78 @@
+use crate::shared::{
+    models::SignatureStrokes,
+    stroke::{replay_duration, replay_paths, signature_paths, signature_view_box, StrokePath},
+};
+use async_std::task;
+use dioxus::prelude::*;
+/// How often a replay redraws the signature, in milliseconds.
+const REPLAY_FRAME_MILLIS: u64 = 16;
+/// Renders a stored signature, either drawn strokes or a legacy base64 encoded PNG.
+///
+/// Signatures drawn as strokes can offer to replay how they were drawn.
+#[component]
+pub fn SignatureImage(
+    signature: String,
+    #[props(default)] class: String,
+    #[props(default)] replayable: bool,
+) -> Element {
+    // The paths drawn so far while replaying, `None` otherwise.
+    let mut replay = use_signal(|| None::<Vec<StrokePath>>);
+    if !SignatureStrokes::is_strokes(&signature) {
+        return rsx! {
+            img {
+                class: "{class}",
+                src: "data:image/png;base64,{signature}",
+                alt: "Signature",
+            }
+        };
+    }
+    let Ok(strokes) = SignatureStrokes::decode(&signature) else {
+        return rsx! {};
+    };
+    let replaying = replay.read().is_some();
+    let paths = replay().unwrap_or_else(|| signature_paths(&strokes));
+    let view_box = signature_view_box(&strokes);
+    let start_replay = move |_| {
+        if replaying {
+            return;
+        }
+        let strokes = strokes.clone();
+        spawn(async move {
+            let duration = replay_duration(&strokes);
+            let started_at = instant::Instant::now();
+            loop {
+                let elapsed = u32::try_from(started_at.elapsed().as_millis()).unwrap_or(u32::MAX);
+                replay.set(Some(replay_paths(&strokes, elapsed)));
+                if elapsed >= duration {
+                    break;
+                }
+                task::sleep(std::time::Duration::from_millis(REPLAY_FRAME_MILLIS)).await;
+            }
+            replay.set(None);
+        });
+    };
+    rsx! {
+        div { class: "relative",
+            svg {
+                class: "{class}",
+                xmlns: "http://www.w3.org/2000/svg",
+                view_box,
+                role: "img",
+                "aria-label": "Signature",
+                for path in paths {
+                    path { d: "{path.d}", fill: path.fill }
+                }
+            }
+            if replayable {
+                button {
+                    class: "absolute top-0 right-0 text-xs text-stone-500 hover:text-alien-green disabled:opacity-50",
+                    r#type: "button",
+                    title: "Replay how this signature was drawn",
+                    disabled: replaying,
+                    onclick: start_replay,
+                    "↻ replay"
+                }
+            }
+        }
+    }
+}
@@ -13,7 +13,9 @@ mod pages;
 mod shared;
 use auth::AuthState;
 use components::layout::NavFooter;
-use pages::{AboutMe, Blog, Guestbook, Home, NotFound, Projects};
+use pages::{
+    AboutMe, Account, Admin, AuthError, Blog, Guestbook, Home, NotFound, Projects, Sessions,
+};
 
 static STYLES: Asset = asset!("/assets/styles");
 
@@ -59,6 +61,14 @@ enum Route {
     AboutMe {},
     #[route("/guestbook")]
     Guestbook {},
+    #[route("/admin")]
+    Admin {},
+    #[route("/sessions")]
+    Sessions {},
+    #[route("/account")]
+    Account {},
+    #[route("/auth/error?:reason")]
+    AuthError { reason: String },
     #[route("/:..route")]
     NotFound { route: Vec<String> },
 }
@@ -77,7 +87,7 @@ fn App() -> Element {
                             "Fetching user signature for authenticated user"
                         );
 
-                        let signature = match server_fns::load_user_signature(user.clone()).await {
+                        let signature = match server_fns::load_user_signature().await {
                             Ok(signature) => signature,
                             Err(e) => {
                                 dioxus_logger::tracing::error!(
@@ -88,9 +98,15 @@ fn App() -> Element {
                             }
                         };
 
+                        let naughty = server_fns::is_naughty().await.unwrap_or_else(|e| {
+                            dioxus_logger::tracing::error!("Failed to load user standing: {:?}", e);
+                            false
+                        });
+
                         let user_state = auth::UserState {
                             guest: user,
                             entry: signature,
+                            naughty,
                         };
 
                         auth_state.set(AuthState::Authenticated(Box::new(user_state)));
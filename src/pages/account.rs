@@ -0,0 +1,105 @@
+use crate::{
+    Route,
+    auth::AuthState,
+    components::{ButtonVariant, Loading, StyledButton},
+    shared::server_fns,
+};
+use dioxus::prelude::*;
+use web_sys::HtmlAnchorElement;
+use web_sys::wasm_bindgen::JsCast;
+/// Lets the current guest export their data and delete their account.
+#[component]
+pub fn Account() -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    rsx! {
+        div { class: "container mx-auto px-4 py-8",
+            article { class: "prose prose-invert prose-stone prose-h2:mb-0 lg:prose-lg mb-8",
+                h1 { class: "text-3xl font-bold mb-6", "your account" }
+            }
+            match &*auth_state.read() {
+                AuthState::Loading => rsx! {
+                    Loading {}
+                },
+                AuthState::Unauthenticated => rsx! {
+                    p { class: "text-lg",
+                        "Sign in on the "
+                        Link { to: Route::Guestbook {}, class: "alien-link", "guestbook" }
+                        " to manage your account."
+                    }
+                },
+                AuthState::Authenticated(_) => rsx! {
+                    h2 { class: "text-xl font-semibold mb-4 text-stone-100", "your data" }
+                    DataControls {}
+                },
+            }
+        }
+    }
+}
+#[component]
+fn DataControls() -> Element {
+    let mut auth_state = use_context::<Signal<AuthState>>();
+    let mut confirming = use_signal(|| false);
+    let delete_label = if confirming() {
+        "Really delete? This cannot be undone"
+    } else {
+        "Delete account"
+    };
+    rsx! {
+        p { class: "text-stone-400 mb-4",
+            "Download everything stored about you, or delete your account together with your signature."
+        }
+        div { class: "flex gap-2",
+            StyledButton {
+                text: "Download my data",
+                variant: ButtonVariant::Secondary,
+                onclick: move |_| {
+                    spawn(async move {
+                        match server_fns::export_my_data().await {
+                            Ok(json) => download_json("gabioinf-data.json", &json),
+                            Err(e) => dioxus_logger::tracing::error!("Error exporting data: {e}"),
+                        }
+                    });
+                },
+            }
+            StyledButton {
+                text: "{delete_label}",
+                variant: ButtonVariant::Secondary,
+                onclick: move |_| {
+                    if !confirming() {
+                        confirming.set(true);
+                        return;
+                    }
+                    spawn(async move {
+                        match server_fns::delete_account().await {
+                            Ok(()) => {
+                                auth_state.set(AuthState::Unauthenticated);
+                                navigator().push(Route::Guestbook {});
+                            }
+                            Err(e) => {
+                                dioxus_logger::tracing::error!("Error deleting account: {e}");
+                                confirming.set(false);
+                            }
+                        }
+                    });
+                },
+            }
+        }
+    }
+}
+/// Offers `json` to the browser as a file download.
+fn download_json(filename: &str, json: &str) {
+    let href = format!(
+        "data:application/json;charset=utf-8,{}",
+        String::from(web_sys::js_sys::encode_uri_component(json))
+    );
+    let Some(anchor) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("a").ok())
+    else {
+        return;
+    };
+    let anchor: HtmlAnchorElement = anchor.unchecked_into();
+    anchor.set_href(&href);
+    anchor.set_download(filename);
+    anchor.click();
+}
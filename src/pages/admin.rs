@@ -0,0 +1,303 @@
+use crate::{
+    components::{ButtonVariant, Loading, StyledButton},
+    shared::{
+        models::{GuestOverview, GuestbookEntry, ReportedEntry},
+        server_fns,
+    },
+};
+use dioxus::prelude::*;
+#[component]
+pub fn Admin() -> Element {
+    let mut guests = use_resource(server_fns::load_guest_overview);
+    let mut entries = use_resource(server_fns::load_all_signatures);
+    let mut reports = use_resource(server_fns::load_reports);
+    rsx! {
+        div { class: "container mx-auto px-4 py-8",
+            article { class: "prose prose-invert prose-stone prose-h2:mb-0 lg:prose-lg mb-8",
+                h1 { class: "text-3xl font-bold mb-6", "moderation" }
+            }
+            match &*guests.read() {
+                None => rsx! {
+                    Loading {}
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-lg text-coral", "{e}" }
+                },
+                Some(Ok(guests_list)) => rsx! {
+                    h2 { class: "text-xl font-semibold mb-4 text-stone-100", "guests" }
+                    div { class: "flex flex-col gap-2 mb-10",
+                        for guest in guests_list.iter().cloned() {
+                            GuestRow {
+                                key: "{guest.id}",
+                                guest,
+                                on_change: move |_| guests.restart(),
+                            }
+                        }
+                    }
+                },
+            }
+            match &*reports.read() {
+                None => rsx! {
+                    Loading {}
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-lg text-coral", "{e}" }
+                },
+                Some(Ok(queue)) => rsx! {
+                    h2 { class: "text-xl font-semibold mb-4 text-stone-100", "reports" }
+                    div { class: "flex flex-col gap-2 mb-10",
+                        if queue.is_empty() {
+                            p { class: "text-stone-400", "Nothing to review." }
+                        }
+                        for reported in queue.iter().cloned() {
+                            ReportRow {
+                                key: "{reported.entry.id.as_value()}",
+                                reported,
+                                on_change: move |_| {
+                                    reports.restart();
+                                    entries.restart();
+                                    guests.restart();
+                                },
+                            }
+                        }
+                    }
+                },
+            }
+            match &*entries.read() {
+                None => rsx! {
+                    Loading {}
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-lg text-coral", "{e}" }
+                },
+                Some(Ok(entries_list)) => rsx! {
+                    h2 { class: "text-xl font-semibold mb-4 text-stone-100", "signatures" }
+                    div { class: "flex flex-col gap-2",
+                        for entry in entries_list.iter().cloned() {
+                            EntryRow {
+                                key: "{entry.id.as_value()}",
+                                entry,
+                                on_change: move |_| entries.restart(),
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+#[component]
+fn GuestRow(guest: GuestOverview, on_change: EventHandler<()>) -> Element {
+    let (id, is_admin, is_naughty) = (guest.id, guest.is_admin, guest.is_naughty);
+    let naughty_label = if is_naughty { "Forgive" } else { "Mark naughty" };
+    let admin_label = if is_admin { "Demote" } else { "Promote" };
+    rsx! {
+        div { class: "flex items-center justify-between bg-jet border border-onyx rounded-lg px-4 py-2",
+            div { class: "text-stone-100",
+                "{guest.username}"
+                if is_admin {
+                    span { class: "ml-2 text-xs text-alien-green", "admin" }
+                }
+                if is_naughty {
+                    span { class: "ml-2 text-xs text-coral", "naughty" }
+                }
+            }
+            div { class: "flex gap-2",
+                StyledButton {
+                    text: "{naughty_label}",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::set_naughty(id, !is_naughty).await {
+                                dioxus_logger::tracing::error!("Error updating guest: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+                StyledButton {
+                    text: "{admin_label}",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::set_admin(id, !is_admin).await {
+                                dioxus_logger::tracing::error!("Error updating guest: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+                StyledButton {
+                    text: "Force logout",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::force_logout(id).await {
+                                dioxus_logger::tracing::error!("Error logging out guest: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+            }
+        }
+    }
+}
+#[component]
+fn ReportRow(reported: ReportedEntry, on_change: EventHandler<()>) -> Element {
+    let (id, entry) = (reported.entry.id, reported.entry);
+    let status = if entry.hidden_at.is_some() { "hidden" } else { "visible" };
+    let count = reported.reports.len();
+    rsx! {
+        div { class: "flex flex-col gap-2 bg-jet border border-onyx rounded-lg px-4 py-2",
+            div { class: "text-stone-100 min-w-0",
+                p { class: "truncate",
+                    "{entry.message}"
+                    span { class: "ml-2 text-xs text-coral", "{status}, {count} reports" }
+                }
+                p { class: "text-sm text-stone-400", "by {entry.author_username}" }
+            }
+            ul { class: "text-sm text-stone-400",
+                for report in reported.reports.iter() {
+                    li { "{report.reason.label()} (reported by {report.reporter_username})" }
+                }
+            }
+            div { class: "flex gap-2",
+                StyledButton {
+                    text: "Dismiss",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::dismiss_reports(id).await {
+                                dioxus_logger::tracing::error!("Error dismissing reports: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+                StyledButton {
+                    text: "Delete entry",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::remove_reported_entry(id, false).await {
+                                dioxus_logger::tracing::error!("Error deleting signature: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+                StyledButton {
+                    text: "Delete and mark naughty",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::remove_reported_entry(id, true).await {
+                                dioxus_logger::tracing::error!("Error deleting signature: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+            }
+        }
+    }
+}
+#[component]
+fn EntryRow(entry: GuestbookEntry, on_change: EventHandler<()>) -> Element {
+    let id = entry.id;
+    let has_reply = entry.reply.is_some();
+    let is_pinned = entry.pinned_at.is_some();
+    let pin_label = if is_pinned { "Unpin" } else { "Pin" };
+    let entry_to_delete = entry.clone();
+    let mut reply = use_signal(|| entry.reply.clone().unwrap_or_default());
+    let mut error = use_signal(|| None::<String>);
+    rsx! {
+        div { class: "flex flex-col gap-2 bg-jet border border-onyx rounded-lg px-4 py-2",
+            div { class: "flex items-center justify-between",
+                div { class: "text-stone-100 min-w-0",
+                    p { class: "truncate",
+                        "{entry.message}"
+                        if is_pinned {
+                            span { class: "ml-2 text-xs text-alien-green", "pinned" }
+                        }
+                    }
+                    p { class: "text-sm text-stone-400", "by {entry.author_username}" }
+                }
+                div { class: "flex gap-2",
+                    StyledButton {
+                        text: "{pin_label}",
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| {
+                            spawn(async move {
+                                match server_fns::set_pinned(id, !is_pinned).await {
+                                    Ok(_) => error.set(None),
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                                on_change.call(());
+                            });
+                        },
+                    }
+                    StyledButton {
+                        text: "Delete",
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| {
+                            let entry_to_delete = entry_to_delete.clone();
+                            spawn(async move {
+                                if let Err(e) = server_fns::delete_signature(entry_to_delete).await {
+                                    dioxus_logger::tracing::error!("Error deleting signature: {e}");
+                                }
+                                on_change.call(());
+                            });
+                        },
+                    }
+                }
+            }
+            div { class: "flex items-center gap-2",
+                input {
+                    class: "grow p-2 placeholder:italic placeholder:text-[#434343] rounded-md bg-jet text-stone-100 border border-onyx focus:border-alien-green focus:outline-none",
+                    r#type: "text",
+                    placeholder: "reply as the owner...",
+                    maxlength: "255",
+                    value: "{reply}",
+                    oninput: move |evt| reply.set(evt.value()),
+                }
+                StyledButton {
+                    text: "Reply",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        let payload = server_fns::ReplyRequest {
+                            message: reply(),
+                        };
+                        spawn(async move {
+                            match server_fns::reply_to_signature(id, payload).await {
+                                Ok(_) => {
+                                    error.set(None);
+                                    on_change.call(());
+                                }
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                        });
+                    },
+                }
+                if has_reply {
+                    StyledButton {
+                        text: "Remove reply",
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| {
+                            spawn(async move {
+                                if let Err(e) = server_fns::delete_reply(id).await {
+                                    dioxus_logger::tracing::error!("Error removing reply: {e}");
+                                }
+                                on_change.call(());
+                            });
+                        },
+                    }
+                }
+            }
+            if let Some(error) = error() {
+                p { class: "text-sm text-coral", "{error}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{Route, shared::models::AuthErrorReason};
+use dioxus::prelude::*;
+/// Shown when signing in fails, with a reason reported by the OAuth callback.
+#[component]
+pub fn AuthError(reason: String) -> Element {
+    let message = reason.parse::<AuthErrorReason>().unwrap_or_default().message();
+    rsx! {
+        div { class: "container mx-auto px-4 py-8",
+            article { class: "prose prose-invert prose-stone prose-h2:mb-0 lg:prose-lg mb-8",
+                h1 { class: "text-3xl font-bold mb-6", "sign-in failed" }
+                p { class: "text-lg", "{message}" }
+                p { class: "text-lg",
+                    "Head back to the "
+                    Link { to: Route::Guestbook {}, class: "alien-link", "guestbook" }
+                    " to try again."
+                }
+            }
+        }
+    }
+}
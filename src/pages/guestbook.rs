@@ -1,8 +1,10 @@
 use crate::{
-    MessageValid,
+    MessageValid, Route,
     auth::AuthState,
-    components::{ButtonVariant, IconVariant, SignatureList, SignaturePopup, StyledButton},
-    shared::server_fns,
+    components::{
+        ButtonVariant, IconVariant, SearchBox, SignatureList, SignaturePopup, StyledButton,
+    },
+    shared::{models::{Provider, SignatureSearch}, server_fns},
 };
 use dioxus::prelude::*;
 #[component]
@@ -11,6 +13,33 @@ pub fn Guestbook() -> Element {
 
     let mut auth_state = use_context::<Signal<AuthState>>();
     let mut show_signature_pad = use_signal(|| false);
+    let mut editing = use_signal(|| false);
+    let mut search = use_signal(SignatureSearch::default);
+    let login_providers = use_resource(move || server_fns::get_login_providers());
+    // Shared with the signature cards, which link authors to their profiles.
+    let providers = use_memo(move || {
+        login_providers
+            .read()
+            .as_ref()
+            .and_then(|providers| providers.as_ref().ok())
+            .cloned()
+            .unwrap_or_default()
+    });
+    use_context_provider(|| providers);
+    let other_providers = move || {
+        providers
+            .read()
+            .iter()
+            .map(|p| p.kind)
+            .filter(|kind| *kind != Provider::Github)
+            .map(|kind| {
+                (
+                    format!("/v1/login?provider={}&next=/guestbook", kind.slug()),
+                    format!("Sign in with {kind}"),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
     let close_popup = move |_| show_signature_pad.set(false);
 
     rsx! {
@@ -24,10 +53,19 @@ pub fn Guestbook() -> Element {
                         AuthState::Authenticated(user_state) => {
                             match &user_state.entry {
                                 None => rsx! {
-                                    StyledButton {
-                                        text: "Sign Guestbook",
-                                        variant: ButtonVariant::Primary,
-                                        onclick: move |_| show_signature_pad.set(true),
+                                    if user_state.naughty {
+                                        p { class: "text-coral",
+                                            "You have been marked as naughty and can no longer sign the guestbook."
+                                        }
+                                    } else {
+                                        StyledButton {
+                                            text: "Sign Guestbook",
+                                            variant: ButtonVariant::Primary,
+                                            onclick: move |_| {
+                                                editing.set(false);
+                                                show_signature_pad.set(true);
+                                            },
+                                        }
                                     }
                                     StyledButton {
                                         text: "Sign out",
@@ -57,6 +95,16 @@ pub fn Guestbook() -> Element {
                                     }
                                 },
                                 Some(_signature) => rsx! {
+                                    if !user_state.naughty {
+                                        StyledButton {
+                                            text: "Edit signature",
+                                            variant: ButtonVariant::Primary,
+                                            onclick: move |_| {
+                                                editing.set(true);
+                                                show_signature_pad.set(true);
+                                            },
+                                        }
+                                    }
                                     StyledButton {
                                         text: "Sign out",
                                         variant: ButtonVariant::Secondary,
@@ -87,36 +135,62 @@ pub fn Guestbook() -> Element {
                             }
                         }
                         _ => rsx! {
-                            a { href: "/v1/login?next=/guestbook",
-                                StyledButton {
-                                    text: "Sign in with GitHub",
-                                    variant: ButtonVariant::Primary,
-                                    onclick: |_| (),
-                                    icon: IconVariant::Rsx(rsx! {
-                                        svg {
-                                            xmlns: "http://www.w3.org/2000/svg",
-                                            width: "20",
-                                            height: "20",
-                                            view_box: "0 0 98 98",
-                                            path {
-                                                d: "M48.854 0C21.839 0 0 22 0 49.217c0 21.756 13.993 40.172 33.405 46.69 2.427.49 3.316-1.059 3.316-2.362 0-1.141-.08-5.052-.08-9.127-13.59 2.934-16.42-5.867-16.42-5.867-2.184-5.704-5.42-7.17-5.42-7.17-4.448-3.015.324-3.015.324-3.015 4.934.326 7.523 5.052 7.523 5.052 4.367 7.496 11.404 5.378 14.235 4.074.404-3.178 1.699-5.378 3.074-6.6-10.839-1.141-22.243-5.378-22.243-24.283 0-5.378 1.94-9.778 5.014-13.2-.485-1.222-2.184-6.275.486-13.038 0 0 4.125-1.304 13.426 5.052a46.97 46.97 0 0 1 12.214-1.63c4.125 0 8.33.571 12.213 1.63 9.302-6.356 13.427-5.052 13.427-5.052 2.67 6.763.97 11.816.485 13.038 3.155 3.422 5.015 7.822 5.015 13.2 0 18.905-11.404 23.06-22.324 24.283 1.78 1.548 3.316 4.481 3.316 9.126 0 6.6-.08 11.897-.08 13.526 0 1.304.89 2.853 3.316 2.364 19.412-6.52 33.405-24.935 33.405-46.691C97.707 22 75.788 0 48.854 0z",
-                                                clip_rule: "evenodd",
-                                                fill_rule: "evenodd",
-                                                fill: "#fff",
+                            div { class: "flex gap-2",
+                                a { href: "/v1/login?next=/guestbook",
+                                    StyledButton {
+                                        text: "Sign in with GitHub",
+                                        variant: ButtonVariant::Primary,
+                                        onclick: |_| (),
+                                        icon: IconVariant::Rsx(rsx! {
+                                            svg {
+                                                xmlns: "http://www.w3.org/2000/svg",
+                                                width: "20",
+                                                height: "20",
+                                                view_box: "0 0 98 98",
+                                                path {
+                                                    d: "M48.854 0C21.839 0 0 22 0 49.217c0 21.756 13.993 40.172 33.405 46.69 2.427.49 3.316-1.059 3.316-2.362 0-1.141-.08-5.052-.08-9.127-13.59 2.934-16.42-5.867-16.42-5.867-2.184-5.704-5.42-7.17-5.42-7.17-4.448-3.015.324-3.015.324-3.015 4.934.326 7.523 5.052 7.523 5.052 4.367 7.496 11.404 5.378 14.235 4.074.404-3.178 1.699-5.378 3.074-6.6-10.839-1.141-22.243-5.378-22.243-24.283 0-5.378 1.94-9.778 5.014-13.2-.485-1.222-2.184-6.275.486-13.038 0 0 4.125-1.304 13.426 5.052a46.97 46.97 0 0 1 12.214-1.63c4.125 0 8.33.571 12.213 1.63 9.302-6.356 13.427-5.052 13.427-5.052 2.67 6.763.97 11.816.485 13.038 3.155 3.422 5.015 7.822 5.015 13.2 0 18.905-11.404 23.06-22.324 24.283 1.78 1.548 3.316 4.481 3.316 9.126 0 6.6-.08 11.897-.08 13.526 0 1.304.89 2.853 3.316 2.364 19.412-6.52 33.405-24.935 33.405-46.691C97.707 22 75.788 0 48.854 0z",
+                                                    clip_rule: "evenodd",
+                                                    fill_rule: "evenodd",
+                                                    fill: "#fff",
+                                                }
                                             }
+                                        }),
+                                    }
+                                }
+                                for (href, label) in other_providers() {
+                                    a { href: "{href}",
+                                        StyledButton {
+                                            text: "{label}",
+                                            variant: ButtonVariant::Secondary,
+                                            onclick: |_| (),
                                         }
-                                    }),
+                                    }
                                 }
                             }
                         },
                     }
                 }
             }
+            if matches!(*auth_state.read(), AuthState::Authenticated(_)) {
+                p { class: "mb-6 text-sm text-stone-400",
+                    Link { to: Route::Sessions {}, class: "alien-link", "Manage your sessions" }
+                    " · "
+                    Link { to: Route::Account {}, class: "alien-link", "Manage your account" }
+                }
+            }
             {
                 if *show_signature_pad.read() {
+                    let initial_message = match &*auth_state.read() {
+                        AuthState::Authenticated(user_state) if editing() => {
+                            user_state.entry.as_ref().map(|e| e.message.clone()).unwrap_or_default()
+                        }
+                        _ => String::new(),
+                    };
                     rsx! {
                         SignaturePopup {
                             on_close: close_popup,
+                            editing: editing(),
+                            initial_message,
                             on_submit: move |(message, signature): (String, String)| async move {
                                 let state_: &mut AuthState = &mut auth_state.write();
                                 if let AuthState::Authenticated(user_state) = state_ {
@@ -124,12 +198,18 @@ pub fn Guestbook() -> Element {
                                         message,
                                         signature: if signature.is_empty() { None } else { Some(signature) },
                                     };
-                                    dioxus_logger::tracing::debug!("Submitting signature");
-                                    let resp = server_fns::submit_signature(
-                                            entry_request,
-                                            user_state.guest.clone(),
-                                        )
-                                        .await;
+                                    let resp = match user_state.entry.as_ref().filter(|_| editing()) {
+                                        Some(current) => {
+                                            dioxus_logger::tracing::debug!("Updating signature");
+                                            server_fns::update_signature(current.id, entry_request)
+                                                .await
+                                                .map(Some)
+                                        }
+                                        None => {
+                                            dioxus_logger::tracing::debug!("Submitting signature");
+                                            server_fns::submit_signature(entry_request).await
+                                        }
+                                    };
                                     match resp {
                                         Ok(Some(entry)) => {
                                             message_valid.write().0 = true;
@@ -166,7 +246,8 @@ pub fn Guestbook() -> Element {
                     rsx! {}
                 }
             }
-            SignatureList {}
+            SearchBox { on_search: move |filters| search.set(filters) }
+            SignatureList { search }
         }
     }
 }
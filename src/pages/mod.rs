@@ -8,6 +8,14 @@ mod projects;
 pub use projects::*;
 mod guestbook;
 pub use guestbook::*;
+mod admin;
+pub use admin::*;
+mod auth_error;
+pub use auth_error::*;
+mod sessions;
+pub use sessions::*;
+mod account;
+pub use account::*;
 mod under_construction;
 use dioxus::prelude::*;
 pub use under_construction::*;
@@ -0,0 +1,102 @@
+use crate::{
+    Route,
+    auth::AuthState,
+    components::{ButtonVariant, Loading, StyledButton},
+    shared::{models::ActiveSession, server_fns},
+};
+use dioxus::prelude::*;
+use time::{OffsetDateTime, UtcOffset};
+/// Lists the sessions the current guest is logged in with and lets them revoke them.
+#[component]
+pub fn Sessions() -> Element {
+    let mut auth_state = use_context::<Signal<AuthState>>();
+    let mut sessions = use_resource(server_fns::list_sessions);
+    rsx! {
+        div { class: "container mx-auto px-4 py-8",
+            article { class: "prose prose-invert prose-stone prose-h2:mb-0 lg:prose-lg mb-8",
+                h1 { class: "text-3xl font-bold mb-6", "your sessions" }
+            }
+            match &*sessions.read() {
+                None => rsx! {
+                    Loading {}
+                },
+                Some(Err(_)) => rsx! {
+                    p { class: "text-lg",
+                        "Sign in on the "
+                        Link { to: Route::Guestbook {}, class: "alien-link", "guestbook" }
+                        " to manage your sessions."
+                    }
+                },
+                Some(Ok(sessions_list)) => rsx! {
+                    div { class: "flex flex-col gap-2 mb-6",
+                        for session in sessions_list.iter().cloned() {
+                            SessionRow {
+                                key: "{session.id}",
+                                session,
+                                on_change: move |_| sessions.restart(),
+                            }
+                        }
+                    }
+                    StyledButton {
+                        text: "Sign out everywhere",
+                        variant: ButtonVariant::Primary,
+                        onclick: move |_| {
+                            spawn(async move {
+                                if let Err(e) = server_fns::revoke_all_sessions().await {
+                                    dioxus_logger::tracing::error!("Error revoking sessions: {e}");
+                                }
+                                auth_state.set(AuthState::Unauthenticated);
+                                sessions.restart();
+                            });
+                        },
+                    }
+                },
+            }
+        }
+    }
+}
+fn format_date(date: OffsetDateTime) -> String {
+    let offset = UtcOffset::local_offset_at(date).unwrap_or(date.offset());
+    date.to_offset(offset)
+        .format(time::macros::format_description!(
+            "[day] [month repr:short], [year], [hour repr:24]:[minute]"
+        ))
+        .unwrap()
+}
+#[component]
+fn SessionRow(session: ActiveSession, on_change: EventHandler<()>) -> Element {
+    let id = session.id;
+    let user_agent = session.user_agent.clone().unwrap_or_else(|| "Unknown device".to_string());
+    let ip = session.ip.clone().unwrap_or_else(|| "unknown network".to_string());
+    let created_at = format_date(session.created_at);
+    let last_seen_at = format_date(session.last_seen_at);
+    rsx! {
+        div { class: "flex items-center justify-between bg-jet border border-onyx rounded-lg px-4 py-2",
+            div { class: "text-stone-100 min-w-0",
+                p { class: "truncate",
+                    "{user_agent}"
+                    if session.current {
+                        span { class: "ml-2 text-xs text-alien-green", "this device" }
+                    }
+                }
+                p { class: "text-sm text-stone-400",
+                    "from {ip}, signed in {created_at}, last active {last_seen_at}"
+                }
+            }
+            if !session.current {
+                StyledButton {
+                    text: "Revoke",
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(e) = server_fns::revoke_session(id).await {
+                                dioxus_logger::tracing::error!("Error revoking session: {e}");
+                            }
+                            on_change.call(());
+                        });
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use super::{ActiveSession, FiledReport, GivenReaction, GuestbookEntry, PublicGuest};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+/// Everything stored about a guest, as handed out by the data export.
+///
+/// Credentials are not part of the export, so the profile is a [`PublicGuest`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AccountExport {
+    /// When the export was made.
+    pub exported_at: OffsetDateTime,
+    /// The guest's profile.
+    pub profile: PublicGuest,
+    /// The guest's guestbook entry, without its signature.
+    pub entry: Option<GuestbookEntry>,
+    /// The signature of the entry as stored, see [`GuestbookEntry::signature`].
+    pub signature: Option<String>,
+    /// The groups the guest belongs to.
+    pub groups: Vec<String>,
+    /// The permissions the guest holds, directly or through their groups.
+    pub permissions: Vec<String>,
+    /// The sessions the guest is logged in with.
+    pub sessions: Vec<ActiveSession>,
+    /// The reactions the guest left on entries.
+    pub reactions: Vec<GivenReaction>,
+    /// The reports the guest filed against entries.
+    pub reports: Vec<FiledReport>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+/// A session a guest is logged in with, as shown on the sessions page.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActiveSession {
+    /// Identifies the session for revocation. This is not the session cookie.
+    pub id: i64,
+    /// When the guest logged in.
+    pub created_at: OffsetDateTime,
+    /// When the session was last used.
+    pub last_seen_at: OffsetDateTime,
+    /// The user agent the guest logged in with, if known.
+    pub user_agent: Option<String>,
+    /// The network the guest logged in from, e.g. `203.0.113.0/24`.
+    pub ip: Option<String>,
+    /// Whether this is the session making the request.
+    pub current: bool,
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+/// Why signing in failed, as shown to the user.
+///
+/// The server reports failed OAuth callbacks with one of these reasons, either in the
+/// query of the error page or in a JSON body, without exposing the underlying cause.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthErrorReason {
+    /// The user declined to authorize the application.
+    Denied,
+    /// The provider reported some other error.
+    Provider,
+    /// The login session expired or the request was forged.
+    State,
+    /// The authorization code could not be exchanged for a token.
+    Token,
+    /// The user's profile could not be fetched from the provider.
+    Profile,
+    /// Anything else.
+    #[default]
+    Internal,
+}
+impl AuthErrorReason {
+    /// The identifier of this reason in URLs and JSON.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AuthErrorReason::Denied => "denied",
+            AuthErrorReason::Provider => "provider",
+            AuthErrorReason::State => "state",
+            AuthErrorReason::Token => "token",
+            AuthErrorReason::Profile => "profile",
+            AuthErrorReason::Internal => "internal",
+        }
+    }
+    /// A human-readable explanation.
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthErrorReason::Denied => "You cancelled signing in. No worries, you can try again any time.",
+            AuthErrorReason::Provider => "The sign-in provider reported an error. Please try again.",
+            AuthErrorReason::State => "Your sign-in session expired or was tampered with. Please start over.",
+            AuthErrorReason::Token => "We could not confirm your sign-in with the provider. Please try again.",
+            AuthErrorReason::Profile => "We could not load your profile from the provider. Please try again later.",
+            AuthErrorReason::Internal => "Something went wrong on our side while signing you in.",
+        }
+    }
+}
+impl std::str::FromStr for AuthErrorReason {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            AuthErrorReason::Denied,
+            AuthErrorReason::Provider,
+            AuthErrorReason::State,
+            AuthErrorReason::Token,
+            AuthErrorReason::Profile,
+            AuthErrorReason::Internal,
+        ]
+            .into_iter()
+            .find(|reason| reason.code() == s)
+            .ok_or(())
+    }
+}
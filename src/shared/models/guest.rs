@@ -4,27 +4,85 @@ use sqlx::{FromRow, Type};
 use time::OffsetDateTime;
 extern crate derive_more;
 use derive_more::{From, Into};
-/// Represents a GitHub user ID.
+/// Represents an OAuth provider a guest can sign in with.
 ///
-/// This type is a newtype wrapper around `i64` to provide type safety and clarity
-/// when dealing with GitHub user IDs.
+/// This enum is used both in the application logic and as a database type,
+/// corresponding to the 'oauthprovider' type in the database.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "server", derive(Type))]
+#[cfg_attr(feature = "server", sqlx(type_name = "oauthprovider", rename_all = "lowercase"))]
+pub enum Provider {
+    /// GitHub OAuth apps.
+    #[default]
+    Github,
+    /// GitLab, either gitlab.com or a self-hosted instance.
+    Gitlab,
+    /// A generic OpenID Connect provider.
+    Oidc,
+}
+impl Provider {
+    /// The identifier used for this provider in URLs and configuration.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Provider::Github => "github",
+            Provider::Gitlab => "gitlab",
+            Provider::Oidc => "oidc",
+        }
+    }
+}
+impl std::fmt::Display for Provider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Provider::Github => write!(f, "GitHub"),
+            Provider::Gitlab => write!(f, "GitLab"),
+            Provider::Oidc => write!(f, "OpenID"),
+        }
+    }
+}
+/// A provider guests can sign in with, as configured for this deployment.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LoginProvider {
+    /// The provider.
+    pub kind: Provider,
+    /// The URL that usernames are appended to for their public profile, e.g.
+    /// `https://github.com`. Providers without public profiles have none.
+    pub profile_base: Option<String>,
+}
+impl LoginProvider {
+    /// The public profile of `username` at this provider, if it has one.
+    pub fn profile_url(&self, username: &str) -> Option<String> {
+        self.profile_base.as_ref().map(|base| format!("{base}/{username}"))
+    }
+}
+/// Represents the ID of a user at their OAuth provider.
+///
+/// This type is a newtype wrapper around `String` to provide type safety and clarity
+/// when dealing with provider-specific user IDs, which are numeric for some providers
+/// and opaque strings for others.
 #[derive(
     Debug,
     Serialize,
     Deserialize,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
     Ord,
+    Default,
     From,
     Into,
 )]
-pub struct GithubId(pub(crate) i64);
-impl GithubId {
-    pub fn as_value(&self) -> i64 {
-        self.0
+#[cfg_attr(feature = "server", derive(Type), sqlx(transparent))]
+pub struct ExternalId(pub(crate) String);
+impl ExternalId {
+    pub fn as_value(&self) -> &str {
+        &self.0
+    }
+}
+impl From<i64> for ExternalId {
+    fn from(id: i64) -> Self {
+        Self(id.to_string())
     }
 }
 /// Represents a guest ID in the system.
@@ -62,8 +120,10 @@ impl GuestId {
 pub struct Guest {
     /// The unique identifier for the guest.
     pub id: GuestId,
-    /// The GitHub ID associated with the guest.
-    pub github_id: GithubId,
+    /// The OAuth provider the guest signed in with.
+    pub provider: Provider,
+    /// The ID of the guest at their OAuth provider.
+    pub external_id: ExternalId,
     /// The full name of the guest.
     pub name: String,
     /// The username of the guest.
@@ -72,19 +132,26 @@ pub struct Guest {
     pub created_at: OffsetDateTime,
     /// The timestamp when the guest record was last updated.
     pub updated_at: OffsetDateTime,
-    /// Access token for the guest.
+    /// Access token for the guest, encrypted at rest. Never serialized.
+    #[serde(skip)]
     pub access_token: String,
+    /// Server-side random secret backing the session auth hash. Rotating it revokes
+    /// all of the guest's sessions. Never serialized.
+    #[serde(skip)]
+    pub session_secret: String,
 }
 impl Default for Guest {
     fn default() -> Self {
         Self {
             id: GuestId(0),
-            github_id: GithubId(0),
+            provider: Provider::default(),
+            external_id: ExternalId::default(),
             name: "".to_string(),
             username: "".to_string(),
             created_at: OffsetDateTime::now_utc(),
             updated_at: OffsetDateTime::now_utc(),
             access_token: "".to_string(),
+            session_secret: "".to_string(),
         }
     }
 }
@@ -92,30 +159,65 @@ impl std::fmt::Debug for Guest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Guest")
             .field("id", &self.id)
-            .field("github_id", &self.github_id)
+            .field("provider", &self.provider)
+            .field("external_id", &self.external_id)
             .field("name", &self.name)
             .field("username", &self.username)
             .field("created_at", &self.created_at)
             .field("updated_at", &self.updated_at)
             .field("access_token", &"********")
+            .field("session_secret", &"********")
             .finish()
     }
 }
-/// Represents a GitHub user as returned by the GitHub API.
-#[derive(Deserialize, Debug)]
+/// Public view of a [`Guest`] that is safe to send to clients.
+///
+/// All server functions hand out this type instead of [`Guest`], so credentials
+/// never leave the server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PublicGuest {
+    /// The unique identifier for the guest.
+    pub id: GuestId,
+    /// The OAuth provider the guest signed in with.
+    pub provider: Provider,
+    /// The full name of the guest.
+    pub name: String,
+    /// The username of the guest.
+    pub username: String,
+    /// The timestamp when the guest record was created.
+    pub created_at: OffsetDateTime,
+    /// The timestamp when the guest record was last updated.
+    pub updated_at: OffsetDateTime,
+}
+impl From<Guest> for PublicGuest {
+    fn from(guest: Guest) -> Self {
+        Self {
+            id: guest.id,
+            provider: guest.provider,
+            name: guest.name,
+            username: guest.username,
+            created_at: guest.created_at,
+            updated_at: guest.updated_at,
+        }
+    }
+}
+/// Represents a user identity as reported by an OAuth provider.
+#[derive(Debug, Clone, PartialEq)]
 pub struct NewGuest {
-    /// The GitHub user ID.
-    pub id: GithubId,
-    /// The GitHub username (login).
-    #[serde(rename(deserialize = "login"))]
+    /// The provider that authenticated the user.
+    pub provider: Provider,
+    /// The user ID at the provider.
+    pub external_id: ExternalId,
+    /// The username at the provider.
     pub username: String,
-    /// The full name of the GitHub user, if available.
+    /// The full name of the user, if available.
     pub name: Option<String>,
 }
 impl From<NewGuest> for Guest {
     fn from(val: NewGuest) -> Self {
         Guest {
-            github_id: val.id,
+            provider: val.provider,
+            external_id: val.external_id,
             name: val.name.unwrap_or_else(|| val.username.clone()),
             username: val.username,
             ..Default::default()
@@ -0,0 +1,22 @@
+use super::GuestId;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+/// Summary of a guest as shown on the admin dashboard.
+///
+/// Unlike [`Guest`](super::Guest), this carries no credentials and is safe to hand
+/// out to moderators.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GuestOverview {
+    /// The unique identifier for the guest.
+    pub id: GuestId,
+    /// The username of the guest.
+    pub username: String,
+    /// The full name of the guest.
+    pub name: String,
+    /// The timestamp when the guest record was created.
+    pub created_at: OffsetDateTime,
+    /// Whether the guest belongs to the admins group.
+    pub is_admin: bool,
+    /// Whether the guest has been marked as naughty.
+    pub is_naughty: bool,
+}
@@ -1,4 +1,4 @@
-use super::GuestId;
+use super::{Cursor, GuestId, Provider};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "server")]
 use sqlx::{FromRow, Type};
@@ -9,7 +9,7 @@ use derive_more::{From, Into};
 ///
 /// This type is a newtype wrapper around `i64` to provide type safety and clarity
 /// when dealing with guestbook IDs.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, From, Into, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, From, Into, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "server", derive(Type), sqlx(transparent))]
 pub struct GuestbookId(pub(crate) i64);
 impl GuestbookId {
@@ -26,7 +26,8 @@ pub struct GuestbookEntry {
     /// The message content of the guestbook entry.
     pub message: String,
     /// An optional signature for the guestbook entry.
-    /// This is typically stored as Base64 encoded image data.
+    /// This is stored as serialized [`SignatureStrokes`](super::SignatureStrokes), or as
+    /// Base64 encoded PNG data for entries signed before strokes were kept.
     pub signature: Option<String>,
     /// The timestamp when the guestbook entry was created.
     pub created_at: OffsetDateTime,
@@ -36,6 +37,26 @@ pub struct GuestbookEntry {
     pub author_id: GuestId,
     /// The username of the guest who authored this entry.
     pub author_username: String,
+    /// The provider the author signed in with.
+    pub author_provider: Provider,
+    /// The site owner's reply to this entry, if any.
+    pub reply: Option<String>,
+    /// The timestamp when the reply was written.
+    pub replied_at: Option<OffsetDateTime>,
+    /// The timestamp when the entry was pinned to the top of the guestbook, if it is.
+    pub pinned_at: Option<OffsetDateTime>,
+    /// The timestamp when the entry was hidden after being reported too often, if it is.
+    pub hidden_at: Option<OffsetDateTime>,
+}
+impl GuestbookEntry {
+    /// The cursor pointing just past this entry.
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            pinned_at: self.pinned_at,
+            created_at: self.created_at,
+            id: self.id,
+        }
+    }
 }
 impl Default for GuestbookEntry {
     fn default() -> Self {
@@ -47,6 +68,11 @@ impl Default for GuestbookEntry {
             updated_at: OffsetDateTime::now_utc(),
             author_id: GuestId(0),
             author_username: "".to_string(),
+            author_provider: Provider::default(),
+            reply: None,
+            replied_at: None,
+            pinned_at: None,
+            hidden_at: None,
         }
     }
 }
@@ -57,10 +83,12 @@ pub struct NewGuestbookEntry {
     pub author_id: GuestId,
     /// The username of the guest creating the entry.
     pub author_username: String,
+    /// The provider the guest creating the entry signed in with.
+    pub author_provider: Provider,
     /// The message content for the new guestbook entry.
     pub message: String,
     /// An optional signature for the new guestbook entry.
-    /// This is typically provided as Base64 encoded image data.
+    /// This is provided as serialized [`SignatureStrokes`](super::SignatureStrokes).
     pub signature: Option<String>,
 }
 impl From<NewGuestbookEntry> for GuestbookEntry {
@@ -70,6 +98,7 @@ impl From<NewGuestbookEntry> for GuestbookEntry {
             signature: entry.signature,
             author_id: entry.author_id,
             author_username: entry.author_username,
+            author_provider: entry.author_provider,
             ..Default::default()
         }
     }
@@ -2,3 +2,21 @@ mod guest;
 pub use guest::*;
 mod guestbook_entry;
 pub use guestbook_entry::*;
+mod guest_overview;
+pub use guest_overview::*;
+mod auth_error;
+pub use auth_error::*;
+mod active_session;
+pub use active_session::*;
+mod account_export;
+pub use account_export::*;
+mod page;
+pub use page::*;
+mod signature_search;
+pub use signature_search::*;
+mod reaction;
+pub use reaction::*;
+mod report;
+pub use report::*;
+mod signature_strokes;
+pub use signature_strokes::*;
@@ -0,0 +1,49 @@
+use super::GuestbookId;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+/// Position after which the next page of guestbook entries starts.
+///
+/// Pinned entries are listed first, most recently pinned first, followed by all other
+/// entries newest first. The listing is ordered by `(pinned_at, created_at, id)`, so the
+/// cursor is the key of the last entry of the previous page. Unlike an offset, it stays
+/// valid when new entries are added in the meantime.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    /// Pin time of the last entry seen, or `None` once past the pinned entries.
+    pub pinned_at: Option<OffsetDateTime>,
+    /// Creation time of the last entry seen.
+    pub created_at: OffsetDateTime,
+    /// ID of the last entry seen, breaking ties between equal timestamps.
+    pub id: GuestbookId,
+}
+/// A page of results along with the cursor to fetch the next one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+    /// Where the next page starts, or `None` if this is the last page.
+    pub next_cursor: Option<Cursor>,
+}
+impl<T> Page<T> {
+    /// Builds a page from up to `per_page + 1` fetched items.
+    ///
+    /// The item past `per_page` is only fetched to tell whether another page follows,
+    /// and is dropped.
+    pub fn from_overfetch(
+        mut items: Vec<T>,
+        per_page: usize,
+        cursor: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let has_more = items.len() > per_page;
+        items.truncate(per_page);
+        let next_cursor = if has_more { items.last().map(cursor) } else { None };
+        Self { items, next_cursor }
+    }
+    /// Transforms the items on the page, keeping the cursor.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
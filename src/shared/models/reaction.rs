@@ -0,0 +1,68 @@
+use super::{GuestbookEntry, GuestbookId};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use sqlx::Type;
+use time::OffsetDateTime;
+/// Represents a reaction guests can leave on a guestbook entry.
+///
+/// This enum is used both in the application logic and as a database type,
+/// corresponding to the 'reactionkind' type in the database.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "server", derive(Type))]
+#[cfg_attr(feature = "server", sqlx(type_name = "reactionkind", rename_all = "lowercase"))]
+pub enum Reaction {
+    Heart,
+    ThumbsUp,
+    Laugh,
+    Wave,
+    Fire,
+}
+impl Reaction {
+    /// All reactions, in the order they are shown.
+    pub const ALL: [Reaction; 5] = [
+        Reaction::Heart,
+        Reaction::ThumbsUp,
+        Reaction::Laugh,
+        Reaction::Wave,
+        Reaction::Fire,
+    ];
+    /// The emoji shown for this reaction.
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Reaction::Heart => "❤️",
+            Reaction::ThumbsUp => "👍",
+            Reaction::Laugh => "😂",
+            Reaction::Wave => "👋",
+            Reaction::Fire => "🔥",
+        }
+    }
+}
+/// How often an entry received a reaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReactionCount {
+    /// The reaction.
+    pub reaction: Reaction,
+    /// The number of guests who left it.
+    pub count: i64,
+    /// Whether the guest viewing the entry is one of them.
+    pub reacted: bool,
+}
+/// A guestbook entry together with the reactions it received.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntryWithReactions {
+    /// The entry.
+    pub entry: GuestbookEntry,
+    /// The reactions left on the entry, omitting those nobody left.
+    pub reactions: Vec<ReactionCount>,
+}
+/// A reaction a guest left on an entry, as handed out by the data export.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GivenReaction {
+    /// The entry the reaction was left on.
+    pub entry_id: GuestbookId,
+    /// The reaction.
+    pub reaction: Reaction,
+    /// When the reaction was left.
+    pub created_at: OffsetDateTime,
+}
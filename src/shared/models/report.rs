@@ -0,0 +1,69 @@
+use super::{GuestbookEntry, GuestbookId};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use sqlx::Type;
+use time::OffsetDateTime;
+/// Why a guest reported a guestbook entry.
+///
+/// This enum is used both in the application logic and as a database type,
+/// corresponding to the 'reportreason' type in the database.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "server", derive(Type))]
+#[cfg_attr(feature = "server", sqlx(type_name = "reportreason", rename_all = "lowercase"))]
+pub enum ReportReason {
+    /// Advertising or other unsolicited content.
+    Spam,
+    /// An offensive message.
+    Offensive,
+    /// An inappropriate drawn signature.
+    Drawing,
+    /// Anything else.
+    Other,
+}
+impl ReportReason {
+    /// All reasons, in the order they are offered.
+    pub const ALL: [ReportReason; 4] = [
+        ReportReason::Spam,
+        ReportReason::Offensive,
+        ReportReason::Drawing,
+        ReportReason::Other,
+    ];
+    /// The label shown for this reason.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "Spam",
+            ReportReason::Offensive => "Offensive message",
+            ReportReason::Drawing => "Inappropriate drawing",
+            ReportReason::Other => "Something else",
+        }
+    }
+}
+/// A single report filed against an entry.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Report {
+    /// The username of the guest who filed the report.
+    pub reporter_username: String,
+    /// Why the entry was reported.
+    pub reason: ReportReason,
+    /// When the report was filed.
+    pub created_at: OffsetDateTime,
+}
+/// A reported entry awaiting review, together with the reports filed against it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportedEntry {
+    /// The entry.
+    pub entry: GuestbookEntry,
+    /// The reports, newest first.
+    pub reports: Vec<Report>,
+}
+/// A report a guest filed, as handed out by the data export.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FiledReport {
+    /// The reported entry.
+    pub entry_id: GuestbookId,
+    /// Why the entry was reported.
+    pub reason: ReportReason,
+    /// When the report was filed.
+    pub created_at: OffsetDateTime,
+}
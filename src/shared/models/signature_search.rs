@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use time::Date;
+#[cfg(feature = "server")]
+use validator::{Validate, ValidationError};
+/// Filters for searching the guestbook.
+///
+/// Empty filters match every entry.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "server", derive(Validate))]
+#[cfg_attr(feature = "server", validate(schema(function = "validate_date_range")))]
+pub struct SignatureSearch {
+    /// Words to look for in the message.
+    #[cfg_attr(
+        feature = "server",
+        validate(length(max = 100, message = "Search text must be at most 100 characters"))
+    )]
+    pub text: String,
+    /// Username of the author, matched case-insensitively.
+    #[cfg_attr(
+        feature = "server",
+        validate(length(max = 255, message = "Username must be at most 255 characters"))
+    )]
+    pub author: String,
+    /// Earliest day an entry may have been written on.
+    pub from: Option<Date>,
+    /// Latest day an entry may have been written on, inclusive.
+    pub to: Option<Date>,
+}
+impl SignatureSearch {
+    /// Whether no filter is set.
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+            && self.author.trim().is_empty()
+            && self.from.is_none()
+            && self.to.is_none()
+    }
+}
+#[cfg(feature = "server")]
+fn validate_date_range(search: &SignatureSearch) -> Result<(), ValidationError> {
+    match (search.from, search.to) {
+        (Some(from), Some(to)) if from > to => {
+            Err(
+                ValidationError::new("date_range")
+                    .with_message("The start date must not be after the end date".into()),
+            )
+        }
+        _ => Ok(()),
+    }
+}
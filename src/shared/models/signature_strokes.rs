@@ -0,0 +1,521 @@
+//! Drawn signatures stored as the strokes they were drawn with.
+//!
+//! Signatures are serialized to a compact text format rather than rasterized, so they
+//! can be rendered crisply at any size:
+//!
+//! ```text
+//! v1|<width>,<height>|<style>|[<colour>,<size>:]<x>,<y>,<pressure>[,<time>];.../...
+//! ```
+//!
+//! Strokes are separated by `/` and their points by `;`. The style lists the options
+//! the strokes were drawn with, see [`StrokeStyle`]. Strokes drawn with a brush other
+//! than the default [`Brush`] are prefixed with the indices of its colour and size.
+//! The optional time of a point is when it was drawn, in milliseconds since the
+//! signature was started, so it can be replayed at its original pace. Signatures saved
+//! before timings were recorded have none.
+use std::fmt;
+use std::str::FromStr;
+/// Marks a signature payload as strokes rather than a base64 encoded PNG, which can
+/// never contain a `|`.
+pub const STROKES_PREFIX: &str = "v1|";
+/// The most strokes a signature may consist of.
+pub const MAX_STROKES: usize = 128;
+/// The most points a signature may consist of, across all strokes.
+pub const MAX_POINTS: usize = 8192;
+/// The largest width or height of the pad a signature may be drawn on.
+pub const MAX_DIMENSION: u32 = 4096;
+/// The longest a signature may have taken to draw, in milliseconds.
+pub const MAX_DURATION_MS: u32 = 10 * 60 * 1000;
+/// The colours signatures can be drawn in.
+pub const STROKE_COLOURS: [&str; 4] = ["#f2f2f2", "#c2f9bb", "#ef6f6c", "#6b7fd7"];
+/// The brush sizes signatures can be drawn with, relative to the size of their style.
+pub const BRUSH_SIZES: [f64; 3] = [0.6, 1.0, 1.6];
+/// An error in the format or content of a serialized signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeFormatError(pub String);
+impl fmt::Display for StrokeFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid signature: {}", self.0)
+    }
+}
+impl std::error::Error for StrokeFormatError {}
+fn invalid(reason: impl Into<String>) -> StrokeFormatError {
+    StrokeFormatError(reason.into())
+}
+/// Easing applied to the taper at the start or end of a stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrokeEasing {
+    Linear,
+    EaseOutCubic,
+}
+impl StrokeEasing {
+    /// The easing function.
+    pub fn function(self) -> fn(f64) -> f64 {
+        match self {
+            StrokeEasing::Linear => |t| t,
+            StrokeEasing::EaseOutCubic => |t| (t - 1.0).powi(3) + 1.0,
+        }
+    }
+    fn code(self) -> &'static str {
+        match self {
+            StrokeEasing::Linear => "l",
+            StrokeEasing::EaseOutCubic => "c",
+        }
+    }
+}
+impl FromStr for StrokeEasing {
+    type Err = StrokeFormatError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "l" => Ok(StrokeEasing::Linear),
+            "c" => Ok(StrokeEasing::EaseOutCubic),
+            _ => Err(invalid(format!("unknown easing '{s}'"))),
+        }
+    }
+}
+/// The options a signature was drawn with, determining the shape of its strokes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeStyle {
+    /// The base diameter of the strokes.
+    pub size: f64,
+    /// How much pressure affects the diameter, from -1 to 1.
+    pub thinning: f64,
+    /// How much to soften the edges of the strokes, from 0 to 1.
+    pub smoothing: f64,
+    /// How much to streamline the strokes, from 0 to 1.
+    pub streamline: f64,
+    /// Whether to simulate pressure based on velocity.
+    pub simulate_pressure: bool,
+    /// The length of the taper at the start of each stroke.
+    pub start_taper: f64,
+    /// The easing of the taper at the start of each stroke.
+    pub start_easing: StrokeEasing,
+    /// The length of the taper at the end of each stroke.
+    pub end_taper: f64,
+    /// The easing of the taper at the end of each stroke.
+    pub end_easing: StrokeEasing,
+}
+/// The colour and size a stroke is drawn with, as indices into [`STROKE_COLOURS`] and
+/// [`BRUSH_SIZES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brush {
+    pub colour: usize,
+    pub size: usize,
+}
+impl Default for Brush {
+    fn default() -> Self {
+        Self { colour: 0, size: 1 }
+    }
+}
+impl Brush {
+    /// The CSS colour of the brush.
+    pub fn colour(self) -> &'static str {
+        STROKE_COLOURS[self.colour]
+    }
+    /// The factor the brush scales the size of a stroke by.
+    pub fn scale(self) -> f64 {
+        BRUSH_SIZES[self.size]
+    }
+    fn validate(self) -> Result<(), StrokeFormatError> {
+        if self.colour >= STROKE_COLOURS.len() || self.size >= BRUSH_SIZES.len() {
+            return Err(invalid("unknown brush"));
+        }
+        Ok(())
+    }
+}
+impl StrokeStyle {
+    /// The style of the signature pad, scaled to a pad of the given size.
+    pub fn for_pad(width: f64, height: f64) -> Self {
+        let size = width.min(height) * 0.025;
+        Self {
+            size,
+            thinning: 0.25,
+            smoothing: 0.5,
+            streamline: 0.5,
+            simulate_pressure: true,
+            start_taper: 0.0,
+            start_easing: StrokeEasing::Linear,
+            end_taper: size * 2.0,
+            end_easing: StrokeEasing::EaseOutCubic,
+        }
+    }
+    /// The style of the strokes drawn with a brush.
+    pub fn with_brush(&self, brush: Brush) -> Self {
+        let scale = brush.scale();
+        Self {
+            size: self.size * scale,
+            start_taper: self.start_taper * scale,
+            end_taper: self.end_taper * scale,
+            ..*self
+        }
+    }
+    fn encode(&self) -> String {
+        format!(
+            "{:.2},{:.2},{:.2},{:.2},{},{:.2},{},{:.2},{}",
+            self.size,
+            self.thinning,
+            self.smoothing,
+            self.streamline,
+            u8::from(self.simulate_pressure),
+            self.start_taper,
+            self.start_easing.code(),
+            self.end_taper,
+            self.end_easing.code(),
+        )
+    }
+    fn decode(s: &str) -> Result<Self, StrokeFormatError> {
+        let mut fields = s.split(',');
+        let mut next = || fields.next().ok_or_else(|| invalid("malformed style"));
+        let style = Self {
+            size: parse_number(next()?)?,
+            thinning: parse_number(next()?)?,
+            smoothing: parse_number(next()?)?,
+            streamline: parse_number(next()?)?,
+            simulate_pressure: match next()? {
+                "0" => false,
+                "1" => true,
+                _ => return Err(invalid("malformed style")),
+            },
+            start_taper: parse_number(next()?)?,
+            start_easing: next()?.parse()?,
+            end_taper: parse_number(next()?)?,
+            end_easing: next()?.parse()?,
+        };
+        if fields.next().is_some() {
+            return Err(invalid("malformed style"));
+        }
+        Ok(style)
+    }
+    fn validate(&self) -> Result<(), StrokeFormatError> {
+        let in_range = |value: f64, min: f64, max: f64| (min..=max).contains(&value);
+        if !(self.size > 0.0 && self.size <= 256.0) {
+            return Err(invalid("stroke size out of range"));
+        }
+        if !in_range(self.thinning, -1.0, 1.0)
+            || !in_range(self.smoothing, 0.0, 1.0)
+            || !in_range(self.streamline, 0.0, 1.0)
+        {
+            return Err(invalid("stroke options out of range"));
+        }
+        let max_taper = f64::from(MAX_DIMENSION);
+        if !in_range(self.start_taper, 0.0, max_taper)
+            || !in_range(self.end_taper, 0.0, max_taper)
+        {
+            return Err(invalid("taper out of range"));
+        }
+        Ok(())
+    }
+}
+/// A point of a stroke, in pixels of the pad the signature was drawn on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeSample {
+    pub x: f64,
+    pub y: f64,
+    /// The pressure of the pen, from 0 to 1.
+    pub pressure: f32,
+    /// When the point was drawn, in milliseconds since the signature was started.
+    pub time: Option<u32>,
+}
+/// A stroke of a signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// The brush the stroke was drawn with.
+    pub brush: Brush,
+    /// The points of the stroke, in the order they were drawn.
+    pub points: Vec<StrokeSample>,
+}
+impl Stroke {
+    fn encode(&self) -> String {
+        let points = self
+            .points
+            .iter()
+            .map(|p| {
+                let point = format!("{:.1},{:.1},{:.2}", p.x, p.y, p.pressure);
+                match p.time {
+                    Some(time) => format!("{point},{time}"),
+                    None => point,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+        if self.brush == Brush::default() {
+            return points;
+        }
+        format!("{},{}:{points}", self.brush.colour, self.brush.size)
+    }
+    fn decode(s: &str) -> Result<Self, StrokeFormatError> {
+        let (brush, points) = match s.split_once(':') {
+            Some((brush, points)) => {
+                let (colour, size) = brush
+                    .split_once(',')
+                    .ok_or_else(|| invalid("malformed brush"))?;
+                let parse_index =
+                    |s: &str| s.parse::<usize>().map_err(|_| invalid("malformed brush"));
+                let brush = Brush {
+                    colour: parse_index(colour)?,
+                    size: parse_index(size)?,
+                };
+                (brush, points)
+            }
+            None => (Brush::default(), s),
+        };
+        Ok(Self {
+            brush,
+            points: points.split(';').map(parse_sample).collect::<Result<_, _>>()?,
+        })
+    }
+}
+/// A signature as the strokes it was drawn with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureStrokes {
+    /// The width of the pad the signature was drawn on.
+    pub width: u32,
+    /// The height of the pad the signature was drawn on.
+    pub height: u32,
+    /// The options the strokes were drawn with.
+    pub style: StrokeStyle,
+    /// The strokes, in the order they were drawn.
+    pub lines: Vec<Stroke>,
+}
+impl SignatureStrokes {
+    /// Whether a signature payload is in the stroke format.
+    pub fn is_strokes(payload: &str) -> bool {
+        payload.starts_with(STROKES_PREFIX)
+    }
+    /// Serializes the signature, rounding coordinates to a tenth of a pixel.
+    pub fn encode(&self) -> String {
+        let lines = self.lines.iter().map(Stroke::encode).collect::<Vec<_>>().join("/");
+        format!(
+            "{STROKES_PREFIX}{},{}|{}|{lines}",
+            self.width,
+            self.height,
+            self.style.encode()
+        )
+    }
+    /// Parses a serialized signature and checks that it is within limits.
+    pub fn decode(payload: &str) -> Result<Self, StrokeFormatError> {
+        let body = payload
+            .strip_prefix(STROKES_PREFIX)
+            .ok_or_else(|| invalid("unknown format"))?;
+        let mut sections = body.split('|');
+        let (Some(size), Some(style), Some(lines), None) =
+            (sections.next(), sections.next(), sections.next(), sections.next())
+        else {
+            return Err(invalid("malformed payload"));
+        };
+        let (width, height) = size.split_once(',').ok_or_else(|| invalid("malformed size"))?;
+        let parse_dimension = |s: &str| s.parse::<u32>().map_err(|_| invalid("malformed size"));
+        let width = parse_dimension(width)?;
+        let height = parse_dimension(height)?;
+        let lines = if lines.is_empty() {
+            Vec::new()
+        } else {
+            lines.split('/').map(Stroke::decode).collect::<Result<_, _>>()?
+        };
+        let strokes = Self {
+            width,
+            height,
+            style: StrokeStyle::decode(style)?,
+            lines,
+        };
+        strokes.validate()?;
+        Ok(strokes)
+    }
+    /// The points of all strokes, in the order they were drawn.
+    pub fn points(&self) -> impl Iterator<Item = &StrokeSample> {
+        self.lines.iter().flat_map(|stroke| &stroke.points)
+    }
+    /// Checks that the signature is within limits, its strokes are drawn with known
+    /// brushes, its points lie on the pad and their times, where recorded, only move
+    /// forward.
+    pub fn validate(&self) -> Result<(), StrokeFormatError> {
+        let dimensions = 1..=MAX_DIMENSION;
+        if !dimensions.contains(&self.width) || !dimensions.contains(&self.height) {
+            return Err(invalid("pad size out of range"));
+        }
+        self.style.validate()?;
+        if self.lines.is_empty() {
+            return Err(invalid("no strokes"));
+        }
+        if self.lines.len() > MAX_STROKES {
+            return Err(invalid("too many strokes"));
+        }
+        if self.points().count() > MAX_POINTS {
+            return Err(invalid("too many points"));
+        }
+        for stroke in &self.lines {
+            stroke.brush.validate()?;
+        }
+        let (width, height) = (f64::from(self.width), f64::from(self.height));
+        for point in self.points() {
+            if !(0.0..=width).contains(&point.x) || !(0.0..=height).contains(&point.y) {
+                return Err(invalid("point outside of the pad"));
+            }
+            if !(0.0..=1.0).contains(&point.pressure) {
+                return Err(invalid("pressure out of range"));
+            }
+        }
+        let times: Vec<u32> = self.points().filter_map(|p| p.time).collect();
+        if times.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(invalid("point times out of order"));
+        }
+        if times.last().is_some_and(|&time| time > MAX_DURATION_MS) {
+            return Err(invalid("drawn for too long"));
+        }
+        Ok(())
+    }
+    /// The area covered by the strokes as `(x, y, width, height)`, padded by the largest
+    /// stroke size so the outlines fit.
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        let (mut min_x, mut min_y) = (f64::MAX, f64::MAX);
+        let (mut max_x, mut max_y) = (f64::MIN, f64::MIN);
+        for point in self.points() {
+            min_x = min_x.min(point.x);
+            min_y = min_y.min(point.y);
+            max_x = max_x.max(point.x);
+            max_y = max_y.max(point.y);
+        }
+        if min_x > max_x {
+            return (0.0, 0.0, f64::from(self.width), f64::from(self.height));
+        }
+        let pad = self
+            .lines
+            .iter()
+            .map(|stroke| self.style.with_brush(stroke.brush).size)
+            .fold(0.0, f64::max);
+        (min_x - pad, min_y - pad, max_x - min_x + 2.0 * pad, max_y - min_y + 2.0 * pad)
+    }
+}
+fn parse_number(s: &str) -> Result<f64, StrokeFormatError> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| invalid(format!("malformed number '{s}'")))
+}
+fn parse_sample(s: &str) -> Result<StrokeSample, StrokeFormatError> {
+    let fields: Vec<&str> = s.split(',').collect();
+    let (x, y, pressure, time) = match fields[..] {
+        [x, y, pressure] => (x, y, pressure, None),
+        [x, y, pressure, time] => (x, y, pressure, Some(time)),
+        _ => return Err(invalid("malformed point")),
+    };
+    let time = time
+        .map(|t| t.parse::<u32>().map_err(|_| invalid(format!("malformed time '{t}'"))))
+        .transpose()?;
+    Ok(StrokeSample {
+        x: parse_number(x)?,
+        y: parse_number(y)?,
+        pressure: parse_number(pressure)? as f32,
+        time,
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn signature() -> SignatureStrokes {
+        let sample = |x, y| StrokeSample {
+            x,
+            y,
+            pressure: 0.5,
+            time: None,
+        };
+        SignatureStrokes {
+            width: 800,
+            height: 400,
+            style: StrokeStyle::for_pad(800.0, 400.0),
+            lines: vec![
+                Stroke {
+                    brush: Brush::default(),
+                    points: vec![sample(10.0, 20.0), sample(30.5, 40.25), sample(50.0, 60.0)],
+                },
+                Stroke {
+                    brush: Brush::default(),
+                    points: vec![sample(100.0, 100.0)],
+                },
+            ],
+        }
+    }
+    #[test]
+    fn test_roundtrip() {
+        let strokes = signature();
+        let encoded = strokes.encode();
+        assert!(SignatureStrokes::is_strokes(&encoded));
+        assert_eq!(
+            encoded,
+            "v1|800,400|10.00,0.25,0.50,0.50,1,0.00,l,20.00,c\
+             |10.0,20.0,0.50;30.5,40.2,0.50;50.0,60.0,0.50/100.0,100.0,0.50"
+        );
+        let decoded = SignatureStrokes::decode(&encoded).unwrap();
+        assert_eq!(decoded.lines.len(), 2);
+        assert_eq!(decoded.lines[0].points[1].y, 40.2);
+        assert_eq!(decoded.style, strokes.style);
+    }
+    #[test]
+    fn test_roundtrip_brushes() {
+        let mut strokes = signature();
+        strokes.lines[1].brush = Brush { colour: 2, size: 0 };
+        let encoded = strokes.encode();
+        assert!(encoded.ends_with("/2,0:100.0,100.0,0.50"));
+        let decoded = SignatureStrokes::decode(&encoded).unwrap();
+        assert_eq!(decoded.lines[0].brush, Brush::default());
+        assert_eq!(decoded.lines[1].brush, Brush { colour: 2, size: 0 });
+        assert_eq!(decoded.lines[1].brush.colour(), "#ef6f6c");
+    }
+    #[test]
+    fn test_roundtrip_times() {
+        let mut strokes = signature();
+        for (i, point) in strokes.lines.iter_mut().flat_map(|s| &mut s.points).enumerate() {
+            point.time = Some(i as u32 * 40);
+        }
+        let encoded = strokes.encode();
+        assert!(encoded.ends_with(
+            "|10.0,20.0,0.50,0;30.5,40.2,0.50,40;50.0,60.0,0.50,80/100.0,100.0,0.50,120"
+        ));
+        let decoded = SignatureStrokes::decode(&encoded).unwrap();
+        assert_eq!(decoded.lines[1].points[0].time, Some(120));
+        // Signatures saved before timings were recorded still decode.
+        let legacy = SignatureStrokes::decode(&signature().encode()).unwrap();
+        assert!(legacy.points().all(|p| p.time.is_none()));
+    }
+    #[test]
+    fn test_rejects_invalid_signatures() {
+        let mut outside = signature();
+        outside.lines[1].points[0].x = 801.0;
+        let mut empty = signature();
+        empty.lines.clear();
+        let mut heavy = signature();
+        heavy.lines[0].points[0].pressure = 1.5;
+        let mut rewound = signature();
+        rewound.lines[0].points[0].time = Some(100);
+        rewound.lines[0].points[1].time = Some(50);
+        let mut slow = signature();
+        slow.lines[1].points[0].time = Some(MAX_DURATION_MS + 1);
+        let mut unknown_brush = signature();
+        unknown_brush.lines[0].brush.colour = STROKE_COLOURS.len();
+        for strokes in [outside, empty, heavy, rewound, slow, unknown_brush] {
+            assert!(SignatureStrokes::decode(&strokes.encode()).is_err());
+        }
+        for payload in [
+            "iVBORw0KGgo=",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,x,20,c|1,1,0.5",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|1,1",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|1,1,0.5,-1",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|1,1,0.5,1,1",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|1:1,1,0.5",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|0,9:1,1,0.5",
+            "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|NaN,1,0.5",
+            "v1|0,400|10,0.25,0.5,0.5,1,0,l,20,c|0,0,0.5",
+        ] {
+            assert!(SignatureStrokes::decode(payload).is_err(), "{payload}");
+        }
+    }
+    #[test]
+    fn test_bounds() {
+        let (x, y, width, height) = signature().bounds();
+        assert_eq!((x, y, width, height), (0.0, 10.0, 110.0, 100.0));
+        let mut thick = signature();
+        thick.lines[1].brush.size = 2;
+        assert_eq!(thick.bounds(), (-6.0, 4.0, 122.0, 112.0));
+    }
+}
@@ -0,0 +1,29 @@
+//! Handlers for guests to export or delete their own account.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::logic::{SessionWrapper, account::export_account},
+    repos::Repository,
+};
+use dioxus::prelude::*;
+/// Deletes the guest's account along with their entry, and logs them out everywhere.
+#[server(DeleteAccount)]
+pub async fn delete_account() -> Result<(), ServerFnError> {
+    let mut session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    state.guest_repo.delete(&user).await?;
+    dioxus_logger::tracing::info!("Guest {} deleted their account", user.id);
+    session.session.logout().await?;
+    Ok(())
+}
+/// Returns everything stored about the guest as pretty-printed JSON.
+#[server(ExportMyData)]
+pub async fn export_my_data() -> Result<String, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let current = session.session.session.id().map(|id| id.to_string());
+    let export = export_account(&state, user, current.as_deref()).await?;
+    Ok(serde_json::to_string_pretty(&export)?)
+}
@@ -0,0 +1,80 @@
+//! Guest moderation handlers for the admin dashboard.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::{Group, PermissionTargets}},
+    errors::ApiError,
+    repos::Repository,
+};
+use crate::shared::models::{GuestId, GuestOverview};
+use dioxus::prelude::*;
+#[server(LoadGuestOverview)]
+pub async fn load_guest_overview() -> Result<Vec<GuestOverview>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    session.require_permission(PermissionTargets::MarkAsNaughty).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let guests = state.guest_repo.read_all().await?;
+    let admins = state.gp_repo.get_group_members(Group::Admins).await?;
+    let naughty = state.gp_repo.get_group_members(Group::NaughtyGuests).await?;
+    Ok(
+        guests
+            .into_iter()
+            .map(|guest| GuestOverview {
+                is_admin: admins.contains(&guest.id),
+                is_naughty: naughty.contains(&guest.id),
+                id: guest.id,
+                username: guest.username,
+                name: guest.name,
+                created_at: guest.created_at,
+            })
+            .collect(),
+    )
+}
+#[server(SetNaughty)]
+pub async fn set_naughty(guest_id: GuestId, naughty: bool) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::MarkAsNaughty).await?;
+    if moderator.id == guest_id {
+        return Err(
+            ApiError::AuthorizationError("Cannot change your own standing".to_string()).into(),
+        );
+    }
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!(
+        "{} sets naughty = {naughty} for guest {guest_id}", moderator.id
+    );
+    Ok(state.gp_repo.set_group_membership(guest_id, Group::NaughtyGuests, naughty).await?)
+}
+#[server(SetAdmin)]
+pub async fn set_admin(guest_id: GuestId, admin: bool) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::ProDemoteUser).await?;
+    if moderator.id == guest_id {
+        return Err(
+            ApiError::AuthorizationError("Cannot change your own standing".to_string()).into(),
+        );
+    }
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!(
+        "{} sets admin = {admin} for guest {guest_id}", moderator.id
+    );
+    Ok(state.gp_repo.set_group_membership(guest_id, Group::Admins, admin).await?)
+}
+/// Logs a guest out of all of their sessions.
+#[server(ForceLogout)]
+pub async fn force_logout(guest_id: GuestId) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::ProDemoteUser).await?;
+    if moderator.id == guest_id {
+        return Err(
+            ApiError::AuthorizationError("Cannot force logout yourself".to_string()).into(),
+        );
+    }
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    state.guest_repo.rotate_session_secret(guest_id).await?;
+    let revoked = state.session_repo.revoke_all(guest_id).await?;
+    dioxus_logger::tracing::info!(
+        "{} forced logout of guest {guest_id} ({revoked} sessions)", moderator.id
+    );
+    Ok(())
+}
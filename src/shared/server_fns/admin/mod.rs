@@ -0,0 +1,8 @@
+mod guests;
+pub use guests::*;
+mod signatures;
+pub use signatures::*;
+mod replies;
+pub use replies::*;
+mod reports;
+pub use reports::*;
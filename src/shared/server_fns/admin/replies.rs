@@ -0,0 +1,57 @@
+//! Handlers for the site owner to reply to guestbook entries.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+};
+use crate::shared::models::{GuestbookEntry, GuestbookId};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use validator::Validate;
+/// Request payload for replying to a guestbook entry.
+///
+/// Replies are validated just like the messages they answer.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "server", derive(Validate))]
+pub struct ReplyRequest {
+    /// The content of the reply.
+    #[cfg_attr(
+        feature = "server",
+        validate(custom(function = "crate::backend::utils::validate_message"))
+    )]
+    pub message: String,
+}
+#[server(ReplyToSignature)]
+pub async fn reply_to_signature(
+    id: GuestbookId,
+    payload: ReplyRequest,
+) -> Result<GuestbookEntry, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::ReplyToSignature).await?;
+    payload.validate()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!("{} replies to signature {:?}", moderator.id, id);
+    let entry = state.guestbook_repo.set_reply(id, Some(payload.message.trim())).await?;
+    Ok(entry)
+}
+#[server(DeleteReply)]
+pub async fn delete_reply(id: GuestbookId) -> Result<GuestbookEntry, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::ReplyToSignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!("{} deletes the reply to signature {:?}", moderator.id, id);
+    Ok(state.guestbook_repo.set_reply(id, None).await?)
+}
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_reply_validation() {
+        let reply = |message: &str| ReplyRequest { message: message.to_string() };
+        assert!(reply("Thanks for stopping by!").validate().is_ok());
+        assert!(reply("").validate().is_err());
+        assert!(reply("   ").validate().is_err());
+        assert!(reply(&"a".repeat(256)).validate().is_err());
+    }
+}
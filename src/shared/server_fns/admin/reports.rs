@@ -0,0 +1,51 @@
+//! Handlers for reviewing the entries guests reported.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::{Group, PermissionTargets}},
+    repos::{GuestbookEntryCriteria, Repository},
+};
+use crate::shared::models::{GuestbookId, ReportedEntry};
+use dioxus::prelude::*;
+#[server(LoadReports)]
+pub async fn load_reports() -> Result<Vec<ReportedEntry>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    session.require_permission(PermissionTargets::DeleteAnySignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    Ok(state.report_repo.queue().await?)
+}
+/// Discards the reports against an entry, showing it again if it was hidden.
+#[server(DismissReports)]
+pub async fn dismiss_reports(entry_id: GuestbookId) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::DeleteAnySignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!("{} dismisses reports on signature {:?}", moderator.id, entry_id);
+    Ok(state.report_repo.dismiss(entry_id).await?)
+}
+/// Deletes a reported entry, optionally marking its author naughty as well.
+#[server(RemoveReportedEntry)]
+pub async fn remove_reported_entry(
+    entry_id: GuestbookId,
+    mark_naughty: bool,
+) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::DeleteAnySignature).await?;
+    if mark_naughty {
+        session.require_permission(PermissionTargets::MarkAsNaughty).await?;
+    }
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let entry = state.guestbook_repo.read(&GuestbookEntryCriteria::WithId(entry_id)).await?;
+    dioxus_logger::tracing::info!(
+        "{} removes reported signature {:?} (mark naughty: {mark_naughty})", moderator.id,
+        entry_id
+    );
+    state.guestbook_repo.delete(&entry).await?;
+    if mark_naughty && entry.author_id != moderator.id {
+        state
+            .gp_repo
+            .set_group_membership(entry.author_id, Group::NaughtyGuests, true)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Guestbook moderation handlers for the admin dashboard.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+    repos::Repository,
+};
+use crate::shared::models::{GuestbookEntry, GuestbookId};
+use dioxus::prelude::*;
+#[server(LoadAllSignatures)]
+pub async fn load_all_signatures() -> Result<Vec<GuestbookEntry>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    session.require_permission(PermissionTargets::DeleteAnySignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    Ok(state.guestbook_repo.read_all().await?)
+}
+/// Pins an entry to the top of the guestbook or unpins it.
+#[server(SetPinned)]
+pub async fn set_pinned(id: GuestbookId, pinned: bool) -> Result<GuestbookEntry, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let moderator = session.require_permission(PermissionTargets::PinSignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    dioxus_logger::tracing::info!("{} sets pinned = {pinned} for signature {:?}", moderator.id, id);
+    Ok(state.guestbook_repo.set_pinned(id, pinned).await?)
+}
@@ -1,20 +1,31 @@
 #[cfg(feature = "server")]
-use crate::backend::domain::logic::SessionWrapper;
-use crate::shared::models::{Guest, GuestId};
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::Group},
+};
+use crate::shared::models::{GuestId, PublicGuest};
 use dioxus::prelude::*;
 #[server(GetUserName)]
-pub async fn get_user() -> Result<Option<Guest>, ServerFnError> {
+pub async fn get_user() -> Result<Option<PublicGuest>, ServerFnError> {
     let session: SessionWrapper = extract().await?;
     match session.session.user {
-        Some(user) => Ok(Some(user)),
+        Some(user) => Ok(Some(user.into())),
         None => Ok(None),
     }
 }
 #[server(GetUserById)]
-pub async fn get_user_by_id(id: GuestId) -> Result<Option<Guest>, ServerFnError> {
+pub async fn get_user_by_id(id: GuestId) -> Result<Option<PublicGuest>, ServerFnError> {
     let session: SessionWrapper = extract().await?;
     match session.session.user {
-        Some(user) if user.id == id => Ok(Some(user)),
+        Some(user) if user.id == id => Ok(Some(user.into())),
         _ => Ok(None),
     }
 }
+#[server(IsNaughty)]
+pub async fn is_naughty() -> Result<bool, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let groups = state.gp_repo.get_user_groups(user.id).await?;
+    Ok(groups.contains(&Group::NaughtyGuests))
+}
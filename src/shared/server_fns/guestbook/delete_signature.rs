@@ -1,19 +1,26 @@
 #[cfg(feature = "server")]
-use crate::backend::errors::ApiError;
+use crate::backend::domain::models::PermissionTargets;
 #[cfg(feature = "server")]
-use crate::backend::{domain::logic::SessionWrapper, repos::Repository, AppState};
+use crate::backend::{
+    AppState,
+    domain::logic::SessionWrapper,
+    repos::{GuestbookEntryCriteria, Repository},
+};
 use crate::shared::models::GuestbookEntry;
 use dioxus::prelude::*;
 #[server(DeleteSignature)]
 pub async fn delete_signature(entry: GuestbookEntry) -> Result<(), ServerFnError> {
     let session: SessionWrapper = extract().await?;
-    match session.session.user {
-        Some(user) if user.id == entry.author_id => {
-            let FromContext(state): FromContext<AppState> = extract().await?;
-            let guestbook_repo = state.guestbook_repo;
-            dioxus_logger::tracing::debug!("Deleting signature: {:?}", entry.id);
-            Ok(guestbook_repo.delete(&entry).await?)
-        }
-        _ => Err(ApiError::AuthorizationError("Unauthorized".to_string()).into()),
-    }
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let guestbook_repo = state.guestbook_repo;
+    let stored = guestbook_repo.read(&GuestbookEntryCriteria::WithId(entry.id)).await?;
+    session
+        .require_entry_permission(
+            &stored,
+            PermissionTargets::DeleteOwnSignature,
+            PermissionTargets::DeleteAnySignature,
+        )
+        .await?;
+    dioxus_logger::tracing::debug!("Deleting signature: {:?}", stored.id);
+    Ok(guestbook_repo.delete(&stored).await?)
 }
@@ -1,23 +1,37 @@
 #[cfg(feature = "server")]
 use crate::backend::{
     AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
     errors::ApiError,
     repos::{GuestbookEntryCriteria, Repository},
 };
-use crate::shared::models::{Guest, GuestbookEntry};
+use crate::shared::models::{Cursor, EntryWithReactions, GuestbookEntry, Page};
 use dioxus::prelude::*;
+/// The largest page clients may request.
+pub const MAX_SIGNATURES_PER_PAGE: usize = 50;
+/// Loads the page of signatures following `after`, or the first page if it is `None`,
+/// along with their reactions.
 #[server(LoadSignatures)]
 pub async fn load_signatures(
-    page: u32,
+    after: Option<Cursor>,
     per_page: usize,
-) -> Result<Vec<GuestbookEntry>, ServerFnError> {
+) -> Result<Page<EntryWithReactions>, ServerFnError> {
+    let per_page = per_page.clamp(1, MAX_SIGNATURES_PER_PAGE);
+    let session: SessionWrapper = extract().await?;
     let FromContext(state): FromContext<AppState> = extract().await?;
     let guestbook_repo = state.guestbook_repo;
-    let signatures = guestbook_repo.read_page(page, per_page).await?;
-    Ok(signatures)
+    let viewer = session.session.user.as_ref().map(|user| user.id);
+    let signatures = if session.has_permission(PermissionTargets::DeleteAnySignature).await? {
+        guestbook_repo.read_page(after, per_page).await?
+    } else {
+        guestbook_repo.read_visible_page(after, per_page, viewer).await?
+    };
+    Ok(state.reaction_repo.attach(signatures, viewer).await?)
 }
 #[server(LoadUserSignature)]
-pub async fn load_user_signature(user: Guest) -> Result<Option<GuestbookEntry>, ServerFnError> {
+pub async fn load_user_signature() -> Result<Option<GuestbookEntry>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
     let FromContext(state): FromContext<AppState> = extract().await?;
     let guestbook_repo = state.guestbook_repo;
     let signature = guestbook_repo
@@ -2,5 +2,13 @@ mod submit_signature;
 pub use submit_signature::*;
 mod load_signatures;
 pub use load_signatures::*;
+mod search_signatures;
+pub use search_signatures::*;
 mod delete_signature;
 pub use delete_signature::*;
+mod update_signature;
+pub use update_signature::*;
+mod reactions;
+pub use reactions::*;
+mod report_signature;
+pub use report_signature::*;
@@ -0,0 +1,40 @@
+//! Handlers for reacting to guestbook entries.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+};
+use crate::shared::models::{GuestbookId, Reaction, ReactionCount};
+use dioxus::prelude::*;
+/// Adds the guest's reaction to an entry and returns the entry's updated reactions.
+///
+/// Only entries shown in the guestbook can be reacted to. Adding a reaction the guest
+/// already left has no effect.
+#[server(AddReaction)]
+pub async fn add_reaction(
+    entry_id: GuestbookId,
+    reaction: Reaction,
+) -> Result<Vec<ReactionCount>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_permission(PermissionTargets::React).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    state.guestbook_repo.read_visible(entry_id, Some(user.id)).await?;
+    state.reaction_repo.add(entry_id, user.id, reaction).await?;
+    let mut counts = state.reaction_repo.counts(&[entry_id], Some(user.id)).await?;
+    Ok(counts.remove(&entry_id).unwrap_or_default())
+}
+/// Removes the guest's reaction from an entry and returns the entry's updated reactions.
+///
+/// Removing a reaction the guest did not leave has no effect.
+#[server(RemoveReaction)]
+pub async fn remove_reaction(
+    entry_id: GuestbookId,
+    reaction: Reaction,
+) -> Result<Vec<ReactionCount>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_permission(PermissionTargets::React).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    state.reaction_repo.remove(entry_id, user.id, reaction).await?;
+    let mut counts = state.reaction_repo.counts(&[entry_id], Some(user.id)).await?;
+    Ok(counts.remove(&entry_id).unwrap_or_default())
+}
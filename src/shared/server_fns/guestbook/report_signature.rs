@@ -0,0 +1,30 @@
+//! Handler for reporting guestbook entries to the moderators.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+    errors::ApiError,
+};
+use crate::shared::models::{GuestbookId, ReportReason};
+use dioxus::prelude::*;
+/// Reports an entry to the moderators.
+///
+/// Entries are hidden from other guests once they received enough reports.
+#[server(ReportSignature)]
+pub async fn report_signature(
+    entry_id: GuestbookId,
+    reason: ReportReason,
+) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_permission(PermissionTargets::ReportSignature).await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    // Entries the guest cannot see cannot be reported, even by a guessed id.
+    let entry = state.guestbook_repo.read_visible(entry_id, Some(user.id)).await?;
+    if entry.author_id == user.id {
+        return Err(ApiError::ValidationError("Cannot report your own entry".to_string()).into());
+    }
+    if state.report_repo.report(entry_id, user.id, reason).await? {
+        dioxus_logger::tracing::info!("Signature {:?} is hidden pending review", entry_id);
+    }
+    Ok(())
+}
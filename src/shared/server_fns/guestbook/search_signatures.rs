@@ -0,0 +1,31 @@
+#[cfg(feature = "server")]
+use super::MAX_SIGNATURES_PER_PAGE;
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+};
+use crate::shared::models::{Cursor, EntryWithReactions, Page, SignatureSearch};
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+use validator::Validate;
+/// Loads the page of signatures matching `search` that follows `after`, along with
+/// their reactions.
+#[server(SearchSignatures)]
+pub async fn search_signatures(
+    search: SignatureSearch,
+    after: Option<Cursor>,
+    per_page: usize,
+) -> Result<Page<EntryWithReactions>, ServerFnError> {
+    search.validate()?;
+    let per_page = per_page.clamp(1, MAX_SIGNATURES_PER_PAGE);
+    let session: SessionWrapper = extract().await?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let include_moderated = session.has_permission(PermissionTargets::DeleteAnySignature).await?;
+    let viewer = session.session.user.map(|user| user.id);
+    let signatures = state
+        .guestbook_repo
+        .search_page(&search, after, per_page, viewer, include_moderated)
+        .await?;
+    Ok(state.reaction_repo.attach(signatures, viewer).await?)
+}
@@ -3,13 +3,19 @@
 //! This module contains the handler function for creating a new guestbook entry,
 //! along with the necessary request payload structure.
 #[cfg(feature = "server")]
+use crate::backend::domain::{logic::SessionWrapper, models::PermissionTargets};
+#[cfg(feature = "server")]
 use crate::backend::AppState;
 #[cfg(feature = "server")]
 use crate::backend::repos::Repository;
-use crate::shared::models::{Guest, GuestbookEntry};
+#[cfg(feature = "server")]
+use crate::backend::utils::sanitize_signature;
+#[cfg(feature = "server")]
+use crate::shared::models::{Guest, NewGuestbookEntry};
+use crate::shared::models::GuestbookEntry;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "server")]
-use validator::Validate;
+use validator::{Validate, ValidationErrors};
 /// Request payload for creating a new guestbook entry.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[cfg_attr(feature = "server", derive(Validate))]
@@ -17,37 +23,72 @@ pub struct CreateEntryRequest {
     /// The message content of the new guestbook entry.
     #[cfg_attr(
         feature = "server",
-        validate(
-            length(
-                min = 1,
-                max = 255,
-                message = "Message must be between 1 and 255 characters"
-            ),
-            custom(
-                function = "crate::backend::utils::validate_not_offensive",
-                message = "watch you mouth"
-            )
-        )
+        validate(custom(function = "crate::backend::utils::validate_message"))
     )]
     pub message: String,
+    /// The drawn signature, as strokes or a legacy base64 encoded PNG.
     pub signature: Option<String>,
 }
+#[cfg(feature = "server")]
+impl CreateEntryRequest {
+    /// Validates the request and sanitizes its signature for storage, see
+    /// [`sanitize_signature`].
+    pub(crate) fn validated(self) -> Result<Self, ValidationErrors> {
+        self.validate()?;
+        let signature = self
+            .signature
+            .as_deref()
+            .map(sanitize_signature)
+            .transpose()
+            .map_err(|e| {
+                let mut errors = ValidationErrors::new();
+                errors.add("signature", e);
+                errors
+            })?;
+        Ok(Self { signature, ..self })
+    }
+    /// Builds a new guestbook entry from the request, authored by `author`.
+    ///
+    /// The author must always come from the authenticated session, never from the
+    /// request itself.
+    pub(crate) fn into_entry(self, author: &Guest) -> NewGuestbookEntry {
+        NewGuestbookEntry {
+            author_id: author.id,
+            author_username: author.username.clone(),
+            author_provider: author.provider,
+            message: self.message.trim().to_string(),
+            signature: self.signature,
+        }
+    }
+}
 use dioxus::prelude::*;
 #[server(SubmitSignature)]
 pub async fn submit_signature(
     payload: CreateEntryRequest,
-    guest: Guest,
 ) -> Result<Option<GuestbookEntry>, ServerFnError> {
-    use crate::shared::models::NewGuestbookEntry;
-    payload.validate()?;
+    let session: SessionWrapper = extract().await?;
+    let author = session.require_permission(PermissionTargets::AddSignature).await?;
+    let payload = payload.validated()?;
     let FromContext(state): FromContext<AppState> = extract().await?;
-    let new_entry = NewGuestbookEntry {
-        author_id: guest.id,
-        author_username: guest.username,
-        message: payload.message.trim().to_string(),
-        signature: payload.signature,
-    }
-    .into();
+    let new_entry = payload.into_entry(&author).into();
     let entry = state.guestbook_repo.create(&new_entry).await?;
     Ok(Some(entry))
 }
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::shared::models::SignatureStrokes;
+    #[test]
+    fn test_signature_validation() {
+        let request = |signature: &str| CreateEntryRequest {
+            message: "Hello".to_string(),
+            signature: Some(signature.to_string()),
+        };
+        let strokes = "v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|10,20,0.5;30,40,0.5";
+        let normalized = SignatureStrokes::decode(strokes).unwrap().encode();
+        assert_eq!(request(strokes).validated().unwrap().signature, Some(normalized));
+        assert!(request("v1|800,400|10,0.25,0.5,0.5,1,0,l,20,c|900,20,0.5").validated().is_err());
+        assert!(request("v1|garbage").validated().is_err());
+        assert!(request("iVBORw0KGgo=").validated().is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Guestbook entry update handler.
+//!
+//! This module contains the handler function for editing one's own guestbook entry.
+#[cfg(feature = "server")]
+use crate::backend::{
+    AppState,
+    domain::{logic::SessionWrapper, models::PermissionTargets},
+    errors::ApiError,
+    repos::{GuestbookEntryCriteria, Repository},
+};
+use crate::shared::models::{GuestbookEntry, GuestbookId};
+use super::CreateEntryRequest;
+use dioxus::prelude::*;
+#[cfg(feature = "server")]
+impl CreateEntryRequest {
+    /// Applies the request to an existing entry.
+    ///
+    /// A missing signature keeps the one already stored.
+    pub(crate) fn apply_to(self, entry: GuestbookEntry) -> GuestbookEntry {
+        GuestbookEntry {
+            message: self.message.trim().to_string(),
+            signature: self.signature.or(entry.signature.clone()),
+            ..entry
+        }
+    }
+}
+#[server(UpdateSignature)]
+pub async fn update_signature(
+    id: GuestbookId,
+    payload: CreateEntryRequest,
+) -> Result<GuestbookEntry, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_permission(PermissionTargets::EditOwnSignature).await?;
+    let payload = payload.validated()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let stored = state.guestbook_repo.read(&GuestbookEntryCriteria::WithId(id)).await?;
+    if stored.author_id != user.id {
+        return Err(
+            ApiError::AuthorizationError("Cannot edit someone else's signature".to_string())
+                .into(),
+        );
+    }
+    dioxus_logger::tracing::debug!("Updating signature: {:?}", stored.id);
+    let entry = state.guestbook_repo.update(&payload.apply_to(stored)).await?;
+    Ok(entry)
+}
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::backend::repos::PgRepository;
+    use crate::backend::utils::setup_guest;
+    use sqlx::PgPool;
+    #[sqlx::test]
+    async fn test_apply_update(pool: PgPool) {
+        let author = setup_guest(&pool).await;
+        let repo = PgRepository::<GuestbookEntry>::new(pool);
+        let entry = GuestbookEntry {
+            message: "Original".to_string(),
+            signature: Some("Original signature".to_string()),
+            author_id: author.id,
+            author_username: author.username.clone(),
+            ..Default::default()
+        };
+        let created = repo.create(&entry).await.unwrap();
+        let request = CreateEntryRequest {
+            message: " Edited ".to_string(),
+            signature: None,
+        };
+        let updated = repo.update(&request.apply_to(created.clone())).await.unwrap();
+        assert_eq!(updated.id, created.id);
+        assert_eq!(updated.message, "Edited");
+        assert_eq!(updated.signature, created.signature);
+        assert_eq!(updated.author_id, author.id);
+    }
+}
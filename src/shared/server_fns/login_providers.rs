@@ -0,0 +1,10 @@
+#[cfg(feature = "server")]
+use crate::backend::domain::logic::SessionWrapper;
+use crate::shared::models::LoginProvider;
+use dioxus::prelude::*;
+/// Lists the OAuth providers guests can sign in with.
+#[server(GetLoginProviders)]
+pub async fn get_login_providers() -> Result<Vec<LoginProvider>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    Ok(session.session.backend.providers().login_providers())
+}
@@ -1,6 +1,14 @@
 mod get_user;
 pub use get_user::*;
+mod login_providers;
+pub use login_providers::*;
 mod logout;
 pub use logout::*;
+mod sessions;
+pub use sessions::*;
+mod account;
+pub use account::*;
 mod guestbook;
 pub use guestbook::*;
+mod admin;
+pub use admin::*;
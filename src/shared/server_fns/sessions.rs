@@ -0,0 +1,51 @@
+//! Handlers for guests to review and revoke the sessions they are logged in with.
+#[cfg(feature = "server")]
+use crate::backend::{AppState, domain::logic::SessionWrapper, errors::ApiError};
+use crate::shared::models::ActiveSession;
+use dioxus::prelude::*;
+#[server(ListSessions)]
+pub async fn list_sessions() -> Result<Vec<ActiveSession>, ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let current = session.session.session.id().map(|id| id.to_string());
+    let sessions = state.session_repo.list_active(user.id).await?;
+    Ok(sessions.into_iter().map(|s| s.into_active(current.as_deref())).collect())
+}
+/// Logs the guest out of one of their other sessions.
+///
+/// The current session cannot be revoked this way; signing out ends it.
+#[server(RevokeSession)]
+pub async fn revoke_session(id: i64) -> Result<(), ServerFnError> {
+    let session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    let current = session.session.session.id().map(|id| id.to_string());
+    let is_current = state
+        .session_repo
+        .list_active(user.id)
+        .await?
+        .into_iter()
+        .any(|s| s.id == id && current.as_ref() == Some(&s.session_id));
+    if is_current {
+        return Err(
+            ApiError::ValidationError("Cannot revoke the current session".to_string()).into(),
+        );
+    }
+    if !state.session_repo.revoke(user.id, id).await? {
+        return Err(ApiError::NotFoundError(format!("Session {id} not found")).into());
+    }
+    Ok(())
+}
+/// Logs the guest out of every session, including the current one.
+#[server(RevokeAllSessions)]
+pub async fn revoke_all_sessions() -> Result<(), ServerFnError> {
+    let mut session: SessionWrapper = extract().await?;
+    let user = session.require_user()?;
+    let FromContext(state): FromContext<AppState> = extract().await?;
+    state.guest_repo.rotate_session_secret(user.id).await?;
+    let revoked = state.session_repo.revoke_all(user.id).await?;
+    dioxus_logger::tracing::info!("Guest {} revoked {revoked} sessions", user.id);
+    session.session.logout().await?;
+    Ok(())
+}
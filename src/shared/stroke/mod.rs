@@ -0,0 +1,16 @@
+//! Geometry of drawn signatures.
+//!
+//! Turns the points of a stroke into the outline of a pressure-sensitive brush stroke,
+//! renders whole signatures as SVG and replays them stroke by stroke. This is pure
+//! math, shared by the signature pad in the browser and the server, which renders
+//! signatures without client JS.
+mod outline;
+pub use outline::*;
+mod point;
+pub use point::Point;
+mod replay;
+pub use replay::*;
+mod svg;
+pub use svg::*;
+mod utils;
+pub use utils::PointExt;
@@ -1,5 +1,5 @@
-use super::{point::Point, utils::rotate_around};
-use crate::components::signature_pad::utils::PointExt;
+use super::{point::Point, utils::{rotate_around, PointExt}};
+use crate::shared::models::StrokeStyle;
 const RATE_OF_PRESSURE_CHANGE: f32 = 0.275;
 const FIXED_PI: f64 = std::f64::consts::PI + 0.0001;
 #[derive(Clone, Debug)]
@@ -35,6 +35,28 @@ impl Default for StrokeOptions {
         }
     }
 }
+impl From<&StrokeStyle> for StrokeOptions {
+    fn from(style: &StrokeStyle) -> Self {
+        StrokeOptions {
+            size: style.size,
+            thinning: style.thinning,
+            smoothing: style.smoothing,
+            streamline: style.streamline,
+            simulate_pressure: style.simulate_pressure,
+            start: CapOptions {
+                taper: Some(style.start_taper),
+                easing: style.start_easing.function(),
+                ..Default::default()
+            },
+            end: CapOptions {
+                taper: Some(style.end_taper),
+                easing: style.end_easing.function(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
 impl Default for CapOptions {
     fn default() -> Self {
         CapOptions {
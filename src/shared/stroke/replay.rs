@@ -0,0 +1,117 @@
+use super::{
+    outline::StrokeOptions,
+    point::Point,
+    svg::{outline_path, StrokePath},
+};
+use crate::shared::models::SignatureStrokes;
+/// The time between points of a signature saved without timings, in milliseconds.
+pub const REPLAY_POINT_MS: u32 = 16;
+/// The longest pause a replay holds between two points, in milliseconds, so a guest
+/// who stopped to think does not stall it.
+pub const REPLAY_MAX_PAUSE_MS: u32 = 1000;
+/// When each point of a signature is drawn during a replay, in milliseconds since the
+/// replay started.
+fn timeline(strokes: &SignatureStrokes) -> Vec<Vec<u32>> {
+    let mut elapsed = 0;
+    let mut previous: Option<u32> = None;
+    let mut first = true;
+    strokes
+        .lines
+        .iter()
+        .map(|stroke| {
+            stroke
+                .points
+                .iter()
+                .map(|point| {
+                    if !first {
+                        let delta = match (previous, point.time) {
+                            (Some(previous), Some(time)) => time.saturating_sub(previous),
+                            _ => REPLAY_POINT_MS,
+                        };
+                        elapsed += delta.min(REPLAY_MAX_PAUSE_MS);
+                    }
+                    first = false;
+                    previous = point.time;
+                    elapsed
+                })
+                .collect()
+        })
+        .collect()
+}
+/// How long a replay of a signature takes, in milliseconds.
+pub fn replay_duration(strokes: &SignatureStrokes) -> u32 {
+    timeline(strokes)
+        .last()
+        .and_then(|line| line.last())
+        .copied()
+        .unwrap_or(0)
+}
+/// The SVG paths of a signature as drawn `elapsed` milliseconds into its replay.
+///
+/// Strokes still being drawn are outlined like on the pad, without their end taper.
+pub fn replay_paths(strokes: &SignatureStrokes, elapsed: u32) -> Vec<StrokePath> {
+    strokes
+        .lines
+        .iter()
+        .zip(timeline(strokes))
+        .map(|(stroke, times)| {
+            let drawn = times.iter().take_while(|&&time| time <= elapsed).count();
+            let options = StrokeOptions {
+                last: drawn == stroke.points.len(),
+                ..StrokeOptions::from(&strokes.style.with_brush(stroke.brush))
+            };
+            let points: Vec<Point> =
+                stroke.points[..drawn].iter().copied().map(Point::from).collect();
+            StrokePath {
+                d: outline_path(&points, &options),
+                fill: stroke.brush.colour(),
+            }
+        })
+        .filter(|path| !path.d.is_empty())
+        .collect()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::models::{Brush, Stroke, StrokeSample, StrokeStyle};
+    use crate::shared::stroke::signature_paths;
+    fn strokes(times: [Option<u32>; 4]) -> SignatureStrokes {
+        let sample = |x, time| StrokeSample {
+            x,
+            y: 50.0,
+            pressure: 0.5,
+            time,
+        };
+        SignatureStrokes {
+            width: 800,
+            height: 400,
+            style: StrokeStyle::for_pad(800.0, 400.0),
+            lines: vec![
+                Stroke {
+                    brush: Brush::default(),
+                    points: vec![sample(20.0, times[0]), sample(40.0, times[1])],
+                },
+                Stroke {
+                    brush: Brush { colour: 3, size: 2 },
+                    points: vec![sample(60.0, times[2]), sample(80.0, times[3])],
+                },
+            ],
+        }
+    }
+    #[test]
+    fn test_replay_timeline() {
+        let timed = strokes([Some(500), Some(600), Some(5000), Some(5100)]);
+        // The replay starts with the first point and caps the pause between strokes.
+        assert_eq!(timeline(&timed), vec![vec![0, 100], vec![1100, 1200]]);
+        assert_eq!(replay_duration(&timed), 1200);
+        let untimed = strokes([None; 4]);
+        assert_eq!(replay_duration(&untimed), 3 * REPLAY_POINT_MS);
+    }
+    #[test]
+    fn test_replay_paths() {
+        let timed = strokes([Some(0), Some(100), Some(200), Some(300)]);
+        assert_eq!(replay_paths(&timed, 50).len(), 1);
+        assert_eq!(replay_paths(&timed, 200).len(), 2);
+        assert_eq!(replay_paths(&timed, 300), signature_paths(&timed));
+    }
+}
@@ -0,0 +1,85 @@
+use super::{
+    outline::{get_stroke, StrokeOptions},
+    point::Point,
+    utils::{get_svg_path_from_stroke, PointExt},
+};
+use crate::shared::models::SignatureStrokes;
+use std::fmt::Write;
+/// The outline of a stroke and the colour to fill it with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokePath {
+    pub d: String,
+    pub fill: &'static str,
+}
+/// The SVG path outlining a stroke through the given points.
+pub fn outline_path(line: &[Point], options: &StrokeOptions) -> String {
+    let outline = get_stroke(line, options)
+        .into_iter()
+        .map(|p| p.as_vector())
+        .collect::<Vec<_>>();
+    get_svg_path_from_stroke(outline, false)
+}
+/// The SVG paths outlining each stroke of a signature.
+pub fn signature_paths(strokes: &SignatureStrokes) -> Vec<StrokePath> {
+    strokes
+        .lines
+        .iter()
+        .map(|stroke| {
+            let options = StrokeOptions::from(&strokes.style.with_brush(stroke.brush));
+            let points: Vec<Point> = stroke.points.iter().copied().map(Point::from).collect();
+            StrokePath {
+                d: outline_path(&points, &options),
+                fill: stroke.brush.colour(),
+            }
+        })
+        .filter(|path| !path.d.is_empty())
+        .collect()
+}
+/// The `viewBox` cropping a signature to its strokes.
+pub fn signature_view_box(strokes: &SignatureStrokes) -> String {
+    let (x, y, width, height) = strokes.bounds();
+    format!("{x:.1} {y:.1} {width:.1} {height:.1}")
+}
+/// Renders a signature as a standalone SVG document.
+pub fn signature_svg(strokes: &SignatureStrokes) -> String {
+    let (_, _, width, height) = strokes.bounds();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{}" width="{width:.0}" height="{height:.0}">"#,
+        signature_view_box(strokes)
+    );
+    for path in signature_paths(strokes) {
+        let _ = write!(svg, r#"<path d="{}" fill="{}"/>"#, path.d, path.fill);
+    }
+    svg.push_str("</svg>");
+    svg
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::models::{Brush, Stroke, StrokeSample, StrokeStyle};
+    #[test]
+    fn test_signature_svg() {
+        let strokes = SignatureStrokes {
+            width: 800,
+            height: 400,
+            style: StrokeStyle::for_pad(800.0, 400.0),
+            lines: vec![Stroke {
+                brush: Brush { colour: 1, size: 1 },
+                points: (0..10)
+                    .map(|i| StrokeSample {
+                        x: 20.0 + 10.0 * i as f64,
+                        y: 50.0,
+                        pressure: 0.5,
+                        time: None,
+                    })
+                    .collect(),
+            }],
+        };
+        let svg = signature_svg(&strokes);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" "#));
+        assert!(svg.contains(r#"viewBox="10.0 40.0 110.0 20.0""#));
+        assert_eq!(svg.matches("<path d=\"M").count(), 1);
+        assert!(svg.contains(r##"fill="#c2f9bb""##));
+        assert!(svg.ends_with("</svg>"));
+    }
+}